@@ -0,0 +1,97 @@
+//! `cargo bench --bench board`, or `cargo bench --bench board -- flood_fill` for a single suite.
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId,
+    Criterion, Throughput,
+};
+use minesweeper_rs::resources::{BoardRules, Coordinates, TileMap};
+
+/// from a beginner board up to the largest boards played.
+const SIZES: [(i64, i64); 5] = [(9, 9), (30, 16), (100, 100), (500, 500), (2000, 2000)];
+
+/// fraction of tiles holding a mine, about that of an expert board.
+const DENSITY: f64 = 0.2;
+
+/// density of the boards flood filled, low enough for the first reveal to
+/// open up most of the board.
+const FLOOD_DENSITY: f64 = 0.01;
+
+/// boards above this many tiles are sampled less, as a single run takes a while.
+const LARGE_BOARD: i64 = 100_000;
+
+fn board(width: i64, height: i64, density: f64) -> TileMap {
+    let mines = (density * (width * height) as f64) as u64;
+    TileMap::new(width, height, 1, mines, BoardRules::default())
+}
+
+fn middle(width: i64, height: i64) -> Coordinates {
+    Coordinates::new(width / 2, height / 2)
+}
+
+/// a board of the given size and density with its mines in place.
+fn mined_board(width: i64, height: i64, density: f64) -> TileMap {
+    let mut tilemap = board(width, height, density);
+    tilemap
+        .generate_mines(&middle(width, height))
+        .expect("mines fit the board");
+    tilemap
+}
+
+/// runs `bench` on every board size, with the throughput counted in tiles.
+fn for_each_size(
+    group: &mut BenchmarkGroup<WallTime>,
+    mut bench: impl FnMut(&mut BenchmarkGroup<WallTime>, BenchmarkId, i64, i64),
+) {
+    for (width, height) in SIZES {
+        let tiles = width * height;
+        group.throughput(Throughput::Elements(tiles as u64));
+        group.sample_size(if tiles > LARGE_BOARD { 10 } else { 50 });
+        bench(
+            group,
+            BenchmarkId::from_parameter(format!("{width}x{height}")),
+            width,
+            height,
+        );
+    }
+}
+
+/// building a board and placing its mines, numbering included.
+fn generation(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("generation");
+    for_each_size(&mut group, |group, id, width, height| {
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| mined_board(width, height, DENSITY));
+        });
+    });
+    group.finish();
+}
+
+/// counting the mines around every tile of a board.
+fn numbering(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("numbering");
+    for_each_size(&mut group, |group, id, width, height| {
+        let mut tilemap = mined_board(width, height, DENSITY);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| tilemap.update_tile_numbers());
+        });
+    });
+    group.finish();
+}
+
+/// the first reveal of a sparse board, opening up most of it.
+fn flood_fill(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("flood_fill");
+    for_each_size(&mut group, |group, id, width, height| {
+        group.bench_function(id, |bencher| {
+            bencher.iter_batched(
+                || mined_board(width, height, FLOOD_DENSITY),
+                |mut tilemap| tilemap.reveal(middle(width, height)),
+                BatchSize::PerIteration,
+            );
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, generation, numbering, flood_fill);
+criterion_main!(benches);
@@ -0,0 +1,422 @@
+//! plays games headlessly with the autoplay bot and reports how it fared.
+//!
+//! `cargo run --release --bin simulate -- --games 1000 --width 30 --height 16 --mines 99`
+
+use std::fmt::Write as _;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use minesweeper_rs::resources::{solve, BoardMode, BotMove, FirstClick, Settings};
+
+const USAGE: &str = "usage: simulate [options]
+
+  --games N            games to play, 100 by default
+  --settings PATH      read the board and its rules from a settings file
+  --width N            board size, overriding the settings
+  --height N
+  --depth N            layers of the board
+  --mines N            mines on the board
+  --density F          fraction of tiles holding a mine, instead of --mines
+  --first-click RULE   classic, opening, chebyshev N, manhattan N or none
+  --format FORMAT      csv or json, csv by default
+  --output PATH        where to write the games, standard output by default";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Csv,
+    Json,
+}
+
+#[derive(Debug)]
+struct Options {
+    games: usize,
+    settings: Settings,
+    density: Option<f64>,
+    format: Format,
+    output: Option<String>,
+}
+
+/// how a single game went.
+#[derive(Debug)]
+struct Game {
+    won: bool,
+    guesses: usize,
+    moves: usize,
+    three_bv: usize,
+    /// time taken to build the board and place its mines.
+    generation: Duration,
+    /// time the bot took to play, generation excluded.
+    play: Duration,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("plays games with the autoplay bot and reports how it fared\n\n{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut games = Vec::with_capacity(options.games);
+    for _ in 0..options.games {
+        match play(&options) {
+            Ok(game) => games.push(game),
+            Err(message) => {
+                eprintln!("{message}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    eprint!("{}", summary(&options, &games));
+
+    let output = match options.format {
+        Format::Csv => to_csv(&games),
+        Format::Json => to_json(&options, &games),
+    };
+    let written = match &options.output {
+        Some(path) => std::fs::write(path, output).map_err(|error| format!("{path}: {error}")),
+        None => {
+            print!("{output}");
+            Ok(())
+        }
+    };
+
+    match written {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("could not write the games to {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// options given on the command line, or none when only help was asked for.
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        games: 100,
+        settings: Settings::default(),
+        density: None,
+        format: Format::Csv,
+        output: None,
+    };
+
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            return Ok(None);
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("`{flag}` needs a value"))?;
+        let settings = &mut options.settings;
+
+        match flag.as_str() {
+            "--games" => options.games = parse_number(&value)?,
+            "--settings" => {
+                if !std::path::Path::new(&value).exists() {
+                    return Err(format!("no settings file at {value}"));
+                }
+                *settings = Settings::load(&value);
+            }
+            "--width" => settings.board_width = parse_number(&value)?,
+            "--height" => settings.board_height = parse_number(&value)?,
+            "--depth" => settings.board_depth = parse_number(&value)?,
+            "--mines" => {
+                settings.mine_count = parse_number(&value)?;
+                options.density = None;
+            }
+            "--density" => {
+                let density: f64 = parse_number(&value)?;
+                if !(0.0..=1.0).contains(&density) {
+                    return Err(format!(
+                        "expected a density between 0 and 1, found `{value}`"
+                    ));
+                }
+                options.density = Some(density);
+            }
+            "--first-click" => settings.rules.first_click = FirstClick::parse(&value)?,
+            "--format" => {
+                options.format = match value.as_str() {
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    _ => return Err(format!("unknown format `{value}`")),
+                };
+            }
+            "--output" => options.output = Some(value),
+            _ => return Err(format!("unknown option `{flag}`")),
+        }
+    }
+
+    let settings = &mut options.settings;
+    if settings.board_width < 1 || settings.board_height < 1 || settings.board_depth < 1 {
+        return Err("a board must have at least one tile".to_string());
+    }
+
+    // endless boards can never be cleared
+    settings.mode = BoardMode::Classic;
+    Ok(Some(options))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected a number, found `{value}`"))
+}
+
+/// plays a game from the first click to its end, as the autoplay bot does in
+/// the game.
+fn play(options: &Options) -> Result<Game, String> {
+    let start = Instant::now();
+    let mut tilemap = options.settings.new_tilemap();
+    if let Some(density) = options.density {
+        let tiles = tilemap.iter().count() as f64;
+        tilemap.mine_count = (density * tiles).round() as u64;
+    }
+    let building = start.elapsed();
+    // mines are placed on the first reveal, in the middle of the game
+    let mut placing = Duration::ZERO;
+
+    let (limit, anti_flags) = (tilemap.rules.mines_per_tile, tilemap.rules.anti_mines > 0);
+    let mut started = false;
+    let mut game = Game {
+        won: false,
+        guesses: 0,
+        moves: 0,
+        three_bv: 0,
+        generation: Duration::ZERO,
+        play: Duration::ZERO,
+    };
+
+    let play_start = Instant::now();
+    'game: loop {
+        let moves = solve(&tilemap);
+        if moves.is_empty() {
+            break;
+        }
+
+        for bot_move in moves {
+            let coordinates = bot_move.coordinates();
+            if !tilemap[coordinates].is_hoverable() {
+                continue;
+            }
+            game.moves += 1;
+
+            match bot_move {
+                BotMove::Mark(_, charge) => {
+                    // an anti-flag comes after the last flag of a full stack
+                    let toggles = match charge < 0 {
+                        true => limit as i32 + 1,
+                        false => charge,
+                    };
+                    for _ in 0..toggles {
+                        tilemap[coordinates].add_flag(limit, anti_flags);
+                    }
+                }
+                BotMove::Reveal(_) | BotMove::Guess(_) => {
+                    if !started {
+                        let start = Instant::now();
+                        tilemap.generate_mines(&coordinates)?;
+                        placing = start.elapsed();
+                        started = true;
+                    }
+
+                    game.guesses += matches!(bot_move, BotMove::Guess(_)) as usize;
+                    let flipped = tilemap.reveal(coordinates);
+                    if flipped.iter().any(|&tile| tilemap[tile].contains_mine()) {
+                        break 'game;
+                    }
+                }
+            }
+        }
+
+        if tilemap.cleared_count() == tilemap.safe_count() {
+            game.won = true;
+            break;
+        }
+    }
+
+    game.generation = building + placing;
+    game.play = play_start.elapsed().saturating_sub(placing);
+    game.three_bv = tilemap.three_bv();
+    Ok(game)
+}
+
+/// value at `fraction` of the way through `sorted`.
+fn percentile<T: Copy>(sorted: &[T], fraction: f64) -> T {
+    let index = ((sorted.len() - 1) as f64 * fraction).round() as usize;
+    sorted[index]
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1e6
+}
+
+/// win rate, guesses, 3BV and timings over all games.
+struct Stats {
+    win_rate: f64,
+    average_guesses: f64,
+    /// 3BV of the boards, lowest first.
+    three_bv: Vec<usize>,
+    average_generation: Duration,
+    slowest_generation: Duration,
+    average_play: Duration,
+}
+
+impl Stats {
+    fn new(games: &[Game]) -> Self {
+        let count = games.len().max(1);
+        let mut three_bv: Vec<usize> = games.iter().map(|game| game.three_bv).collect();
+        three_bv.sort_unstable();
+
+        Self {
+            win_rate: games.iter().filter(|game| game.won).count() as f64 / count as f64,
+            average_guesses: games.iter().map(|game| game.guesses).sum::<usize>() as f64
+                / count as f64,
+            three_bv,
+            average_generation: games.iter().map(|game| game.generation).sum::<Duration>()
+                / count as u32,
+            slowest_generation: games
+                .iter()
+                .map(|game| game.generation)
+                .max()
+                .unwrap_or_default(),
+            average_play: games.iter().map(|game| game.play).sum::<Duration>() / count as u32,
+        }
+    }
+
+    /// lowest, quartiles and highest 3BV.
+    fn three_bv_spread(&self) -> [usize; 5] {
+        if self.three_bv.is_empty() {
+            return [0; 5];
+        }
+
+        [0.0, 0.25, 0.5, 0.75, 1.0].map(|fraction| percentile(&self.three_bv, fraction))
+    }
+
+    fn three_bv_mean(&self) -> f64 {
+        self.three_bv.iter().sum::<usize>() as f64 / self.three_bv.len().max(1) as f64
+    }
+}
+
+fn board_description(options: &Options) -> String {
+    let settings = &options.settings;
+    let mines = match options.density {
+        Some(density) => format!("density {density}"),
+        None => format!("{} mines", settings.mine_count),
+    };
+    format!(
+        "{}x{}x{}, {mines}, first click {}",
+        settings.board_width,
+        settings.board_height,
+        settings.board_depth,
+        settings.rules.first_click.name()
+    )
+}
+
+fn summary(options: &Options, games: &[Game]) -> String {
+    let stats = Stats::new(games);
+    let [least, lower, median, upper, most] = stats.three_bv_spread();
+
+    let mut summary = String::new();
+    let _ = writeln!(
+        summary,
+        "{} games on {}",
+        games.len(),
+        board_description(options)
+    );
+    let _ = writeln!(summary, "win rate        {:.1}%", stats.win_rate * 100.0);
+    let _ = writeln!(
+        summary,
+        "guesses         {:.2} per game",
+        stats.average_guesses
+    );
+    let _ = writeln!(
+        summary,
+        "3bv             mean {:.1}, min {least}, quartiles {lower} / {median} / {upper}, max {most}",
+        stats.three_bv_mean()
+    );
+    let _ = writeln!(
+        summary,
+        "generation      {:.1}us on average, {:.1}us at most",
+        micros(stats.average_generation),
+        micros(stats.slowest_generation)
+    );
+    let _ = writeln!(
+        summary,
+        "play            {:.1}us on average",
+        micros(stats.average_play)
+    );
+    summary
+}
+
+fn to_csv(games: &[Game]) -> String {
+    let mut csv = "game,won,guesses,moves,three_bv,generation_us,play_us\n".to_string();
+    for (index, game) in games.iter().enumerate() {
+        let _ = writeln!(
+            csv,
+            "{index},{},{},{},{},{:.1},{:.1}",
+            game.won,
+            game.guesses,
+            game.moves,
+            game.three_bv,
+            micros(game.generation),
+            micros(game.play)
+        );
+    }
+    csv
+}
+
+fn to_json(options: &Options, games: &[Game]) -> String {
+    let stats = Stats::new(games);
+    let [least, lower, median, upper, most] = stats.three_bv_spread();
+    let settings = &options.settings;
+
+    let mut json = "{\n".to_string();
+    let _ = writeln!(
+        json,
+        "  \"board\": {{\"width\": {}, \"height\": {}, \"depth\": {}, \"mines\": {}, \"density\": {}, \"first_click\": \"{}\"}},",
+        settings.board_width,
+        settings.board_height,
+        settings.board_depth,
+        match options.density {
+            Some(_) => "null".to_string(),
+            None => settings.mine_count.to_string(),
+        },
+        options.density.map_or("null".to_string(), |density| density.to_string()),
+        settings.rules.first_click.name()
+    );
+    let _ = writeln!(
+        json,
+        "  \"summary\": {{\"games\": {}, \"win_rate\": {:.4}, \"average_guesses\": {:.4}, \"three_bv\": {{\"mean\": {:.2}, \"min\": {least}, \"p25\": {lower}, \"median\": {median}, \"p75\": {upper}, \"max\": {most}}}, \"average_generation_us\": {:.1}, \"max_generation_us\": {:.1}, \"average_play_us\": {:.1}}},",
+        games.len(),
+        stats.win_rate,
+        stats.average_guesses,
+        stats.three_bv_mean(),
+        micros(stats.average_generation),
+        micros(stats.slowest_generation),
+        micros(stats.average_play)
+    );
+
+    json.push_str("  \"games\": [\n");
+    for (index, game) in games.iter().enumerate() {
+        let separator = if index + 1 < games.len() { "," } else { "" };
+        let _ = writeln!(
+            json,
+            "    {{\"won\": {}, \"guesses\": {}, \"moves\": {}, \"three_bv\": {}, \"generation_us\": {:.1}, \"play_us\": {:.1}}}{separator}",
+            game.won,
+            game.guesses,
+            game.moves,
+            game.three_bv,
+            micros(game.generation),
+            micros(game.play)
+        );
+    }
+    json.push_str("  ]\n}\n");
+    json
+}
@@ -1,5 +1,78 @@
-use bevy::prelude::Component;
+use bevy::prelude::{Color, Component, IVec2, Vec2};
 
+#[derive(Debug, Component)]
+pub struct Hover;
+
+/// dot over a tile counted by the hovered number, shown with stencils
+/// other than the standard one.
+#[derive(Debug, Component)]
+pub struct StencilMarker;
+
+/// fills up over a tile while a touch is held on it, flagging it when full.
+#[derive(Debug, Component)]
+pub struct LongPressIndicator;
+
+/// camera showing the board, as opposed to the one rendering the glyph atlas.
+#[derive(Debug, Component)]
+pub struct MainCamera;
+
+/// mesh drawing a square of `CHUNK_SIZE` x `CHUNK_SIZE` tiles.
+#[derive(Debug, Component)]
+pub struct Chunk(pub IVec2);
+
+/// mesh drawing a faded copy of the opposite edges around a board that
+/// wraps around.
+#[derive(Debug, Component)]
+pub struct Ghost;
+
+/// mesh drawing a faded copy of the layer this many layers away from the
+/// shown one, beside the board.
+#[derive(Debug, Component)]
+pub struct LayerGhost(pub i64);
+
+/// root node of the settings menu overlay.
+#[derive(Debug, Component)]
+pub struct MenuRoot;
+
+/// selectable row of the settings menu, indexing into `MenuItem::ALL`.
+#[derive(Debug, Component)]
+pub struct MenuEntry(pub usize);
+
+/// text under the menu entries explaining why a game could not start.
+#[derive(Debug, Component)]
+pub struct MenuError;
+
+/// HUD text showing mines left to flag.
+#[derive(Debug, Component)]
+pub struct HudMines;
 
+/// HUD text showing the layer in view on a layered board.
 #[derive(Debug, Component)]
-pub struct Hover;
\ No newline at end of file
+pub struct HudLayer;
+
+/// HUD text showing the game time.
+#[derive(Debug, Component)]
+pub struct HudTimer;
+
+/// looping background music player.
+#[derive(Debug, Component)]
+pub struct Music;
+
+/// card summing up a won game.
+#[derive(Debug, Component)]
+pub struct ResultsCard;
+
+/// pooled particle entity, hidden and back in `ParticlePool` while unused.
+#[derive(Debug, Component, Default)]
+pub struct Particle {
+    pub velocity: Vec2,
+    /// downwards acceleration, in world units per second squared.
+    pub gravity: f32,
+    /// fraction of the velocity lost per second.
+    pub drag: f32,
+    /// rotation speed, in radians per second.
+    pub spin: f32,
+    pub color: Color,
+    pub age: f32,
+    pub lifetime: f32,
+}
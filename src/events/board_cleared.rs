@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::resources::Coordinates;
+
+/// sent when the last tile without a mine is revealed, winning the game.
+#[derive(Event, Debug)]
+pub struct BoardClearedEvent {
+    /// the tile revealed last.
+    pub coordinates: Coordinates,
+}
@@ -2,7 +2,15 @@ use bevy::prelude::*;
 
 use crate::resources::Coordinates;
 
+/// flags an unflagged tile and unflags a flagged one. on boards with several
+/// mines per tile, stacks another flag instead, see `Tile::add_flag`.
 #[derive(Event, Debug)]
 pub struct ToggleMarkEvent {
     pub coordinates: Coordinates,
 }
+
+/// takes one flag off a tile.
+#[derive(Event, Debug)]
+pub struct RemoveMarkEvent {
+    pub coordinates: Coordinates,
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+use crate::resources::Coordinates;
+
+/// sent when a mine is revealed, ending the game.
+#[derive(Event, Debug)]
+pub struct MineHitEvent {
+    pub coordinates: Coordinates,
+}
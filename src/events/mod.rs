@@ -1,13 +1,21 @@
 mod auto_reveal;
-mod display_number;
+mod board_cleared;
 mod flip_tile;
 mod game_start;
 mod mark_tile;
+mod mine_hit;
+mod new_game;
 mod spawn_effects;
+mod tile_action;
+mod tile_changed;
 
 pub use auto_reveal::RevealNeighborsEvent;
-pub use display_number::DisplayNumberEvent;
+pub use board_cleared::BoardClearedEvent;
 pub use flip_tile::FlipTileEvent;
 pub use game_start::GameStartEvent;
-pub use mark_tile::ToggleMarkEvent;
-pub use spawn_effects::SpawnEffectsEvent;
+pub use mark_tile::{RemoveMarkEvent, ToggleMarkEvent};
+pub use mine_hit::MineHitEvent;
+pub use new_game::NewGameEvent;
+pub use spawn_effects::{Effect, SpawnEffectsEvent};
+pub use tile_action::{TileAction, TileActionEvent};
+pub use tile_changed::{TileChange, TileChangedEvent};
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// throws the current board away and starts over with the current settings.
+#[derive(Event, Debug)]
+pub struct NewGameEvent;
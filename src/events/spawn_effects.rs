@@ -2,7 +2,21 @@ use bevy::prelude::*;
 
 use crate::resources::Coordinates;
 
+/// particle effect preset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// a few sparks rising from a revealed tile.
+    RevealBurst,
+    /// a ring of sparks pushed outwards with debris falling behind it.
+    Explosion,
+    /// colorful flakes thrown upwards that drift down slowly.
+    Confetti,
+    /// a single glowing speck floating upwards.
+    Sparkle,
+}
+
 #[derive(Event, Debug)]
 pub struct SpawnEffectsEvent {
     pub coordinates: Coordinates,
+    pub effect: Effect,
 }
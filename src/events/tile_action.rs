@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use crate::resources::Coordinates;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileAction {
+    Reveal,
+    Flag,
+    /// takes a flag off a stack, on boards with several mines per tile.
+    Unflag,
+    Chord,
+}
+
+/// device independent request to act on a tile, sent by every input system.
+#[derive(Event, Debug)]
+pub struct TileActionEvent {
+    pub action: TileAction,
+    pub coordinates: Coordinates,
+}
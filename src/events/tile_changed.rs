@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::resources::Coordinates;
+
+/// what happened to a tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileChange {
+    /// the tile was revealed, as part of the opening started at `origin`.
+    Revealed {
+        origin: Coordinates,
+    },
+    Marked,
+    Unmarked,
+}
+
+/// sent whenever a tile's state changes, so its chunk mesh gets rebuilt.
+#[derive(Event, Debug)]
+pub struct TileChangedEvent {
+    pub coordinates: Coordinates,
+    pub change: TileChange,
+}
@@ -0,0 +1,41 @@
+// bevy systems take their dependencies as parameters, so long signatures are expected
+#![allow(clippy::too_many_arguments)]
+
+mod components;
+pub mod events;
+pub mod plugins;
+pub mod resources;
+pub mod systems;
+
+pub const ROWS: i64 = 15;
+pub const COLS: i64 = 15;
+
+pub const TILE_SIZE: f32 = 40.0;
+
+/// tiles along each side of a chunk, the unit the board is meshed in.
+pub const CHUNK_SIZE: i64 = 32;
+
+pub const WINDOW_WIDTH: f32 = TILE_SIZE * COLS as f32;
+pub const WINDOW_HEIGHT: f32 = TILE_SIZE * ROWS as f32;
+
+/// tiles of the opposite edges shown around a board that wraps around.
+pub const GHOST_TILES: i64 = 2;
+
+/// height of the bar above the board, in logical pixels.
+pub const HUD_HEIGHT: f32 = 48.0;
+
+/// closest zoom of the camera, in world units per screen pixel.
+pub const MIN_CAMERA_SCALE: f32 = 0.25;
+
+/// bloom of the board camera once a game is underway.
+pub const BLOOM_INTENSITY: f32 = 0.1;
+
+pub const MINE_COUNT: u64 = 40;
+
+pub const SETTINGS_PATH: &str = "settings.cfg";
+/// personal best times, see `PersonalBests`.
+pub const RECORDS_PATH: &str = "records.cfg";
+
+pub const FONT_SIZE: f32 = 35.0;
+pub const FONT_PATH: &str =
+    r"C:\Users\MSI\Desktop\Code\Rust\minesweeper-rs\assets\FiraCode-Medium.ttf";
@@ -1,67 +1,132 @@
+use bevy::audio::AddAudioSource;
 use bevy::prelude::*;
-mod components;
-pub mod events;
-mod plugins;
-pub mod resources;
-pub mod systems;
 
-use events::*;
-use plugins::MinesweeperPlugins;
-use resources::GameState;
-use systems::*;
-
-pub const ROWS: i64 = 15;
-pub const COLS: i64 = 15;
-
-pub const TILE_SIZE: f32 = 40.0;
-
-pub const WINDOW_WIDTH: f32 = TILE_SIZE * COLS as f32;
-pub const WINDOW_HEIGHT: f32 = TILE_SIZE * ROWS as f32;
-
-pub const MINE_COUNT: u64 = 40;
-
-pub const FONT_SIZE: f32 = 35.0;
-pub const FONT_PATH: &str =
-    r"C:\Users\MSI\Desktop\Code\Rust\minesweeper-rs\assets\FiraCode-Medium.ttf";
+use minesweeper_rs::events::*;
+use minesweeper_rs::plugins::MinesweeperPlugins;
+use minesweeper_rs::resources::{
+    Autoplay, CameraShake, Cursor, GameClock, GameState, LossSequence, Menu, MouseDrag,
+    PersonalBests, Settings, SoundClip, TileAnimations, TouchPress, WinSequence,
+};
+use minesweeper_rs::systems::*;
+use minesweeper_rs::{RECORDS_PATH, SETTINGS_PATH};
 
 fn main() {
     App::new()
         .add_plugins(MinesweeperPlugins)
+        .add_audio_source::<SoundClip>()
         .add_event::<FlipTileEvent>()
         .add_event::<ToggleMarkEvent>()
+        .add_event::<RemoveMarkEvent>()
         .add_event::<GameStartEvent>()
-        .add_event::<DisplayNumberEvent>()
         .add_event::<RevealNeighborsEvent>()
         .add_event::<SpawnEffectsEvent>()
+        .add_event::<TileActionEvent>()
+        .add_event::<TileChangedEvent>()
+        .add_event::<NewGameEvent>()
+        .add_event::<MineHitEvent>()
+        .add_event::<BoardClearedEvent>()
         .init_resource::<GameState>()
+        .init_resource::<GameClock>()
+        .init_resource::<Cursor>()
+        .init_resource::<Menu>()
+        .init_resource::<TouchPress>()
+        .init_resource::<MouseDrag>()
+        .init_resource::<CameraShake>()
+        .init_resource::<LossSequence>()
+        .init_resource::<WinSequence>()
+        .init_resource::<TileAnimations>()
+        .init_resource::<Autoplay>()
+        .insert_resource(Settings::load(SETTINGS_PATH))
+        .insert_resource(PersonalBests::load(RECORDS_PATH))
         .add_systems(
             Startup,
             (
                 (
-                    (setup_materials, setup_mesh),
-                    (setup_tilemap, spawn_hover_tile),
+                    (setup_materials, setup_mesh, setup_camera, load_font),
+                    (
+                        setup_tilemap,
+                        setup_glyph_atlas,
+                        spawn_hover_tile,
+                        spawn_long_press_indicator,
+                    ),
+                    fit_camera,
                 )
                     .chain(),
-                setup_camera,
-                load_font,
+                spawn_hud,
+                spawn_menu,
+                spawn_particle_pool,
+                spawn_results_card,
+                setup_audio,
             ),
         )
         .add_systems(
             Update,
             (
-                handle_mouse_movement,
                 (
-                    handle_click,
+                    (
+                        handle_camera_keys,
+                        handle_mouse_wheel,
+                        handle_mouse_pan,
+                        handle_gamepad_camera,
+                        handle_touch_camera,
+                    )
+                        .run_if(menu_closed),
+                    refit_camera,
+                    update_camera,
+                    handle_mouse_movement,
+                    update_hover,
+                    update_stencil_overlay,
+                    update_long_press_indicator,
+                    update_camera_shake,
+                )
+                    .chain()
+                    // refitting needs the board of a new game in place
+                    .after(handle_new_game),
+                (
+                    // the menu reads input after gameplay so the press that
+                    // closes it is not also applied to the board
+                    (
+                        handle_click,
+                        handle_keyboard,
+                        handle_gamepad,
+                        handle_touch,
+                        handle_layer_scroll,
+                    )
+                        .run_if(menu_closed),
+                    handle_menu_input,
+                    handle_new_game,
+                    handle_tile_action,
+                    run_autoplay.run_if(menu_closed),
                     handle_game_start,
                     handle_auto_reveal,
                     handle_flip_tile,
                     handle_toggle_mark,
-                    handle_display_number,
+                    handle_mine_hit,
+                    update_loss_sequence,
+                    handle_board_cleared,
+                    update_win_sequence,
+                    start_tile_animations,
+                    load_visible_chunks,
+                    rebuild_chunks,
+                    rebuild_ghost,
+                    rebuild_layer_ghosts,
+                    finish_tile_animations,
                 )
                     .chain(),
                 handle_window_close,
+                update_tile_mesh.after(handle_new_game),
+                (tick_game_clock, update_hud, update_results_card).chain(),
+                update_hud_layer,
                 handle_spawn_effects,
+                play_sound_effects,
                 update_particles,
+                (
+                    handle_settings_hotkeys.run_if(menu_closed),
+                    apply_settings,
+                    update_music_volume,
+                    update_menu,
+                )
+                    .chain(),
             ),
         )
         .run();
@@ -0,0 +1,15 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use super::BotMove;
+
+/// moves of the autoplay bot waiting to be played, one per tick of `timer`.
+#[derive(Debug, Resource, Default)]
+pub struct Autoplay {
+    pub timer: Timer,
+    /// moves found by the last call to `solve`, played before solving again.
+    pub moves: VecDeque<BotMove>,
+    /// whether the bot played a move of the current game, which then sets no record.
+    pub played: bool,
+}
@@ -0,0 +1,29 @@
+/// kind of board a new game is played on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoardMode {
+    /// a fixed size board with a fixed number of mines.
+    #[default]
+    Classic,
+    /// a board without edges, scored by the tiles cleared before the first mine.
+    Endless,
+}
+
+impl BoardMode {
+    pub const ALL: [BoardMode; 2] = [Self::Classic, Self::Endless];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&mode| mode == self).unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::Endless => "endless",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+}
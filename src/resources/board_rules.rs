@@ -0,0 +1,37 @@
+use super::{BoardShape, FirstClick, Stencil, Topology};
+
+/// rules a board is played by, fixed when the board is created.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardRules {
+    pub topology: Topology,
+    /// whether the edges wrap around, so the first and last rows and columns
+    /// touch. hex boards only line up across the top and bottom edges with an
+    /// even number of rows. endless boards have no edges to wrap.
+    pub wrap: bool,
+    pub stencil: Stencil,
+    /// outline of classic boards. endless boards have none.
+    pub shape: BoardShape,
+    /// tiles kept clear of mines around the first tile revealed.
+    pub first_click: FirstClick,
+    /// most mines a single tile can hold. above one, flags stack as well.
+    /// endless boards hold at most one mine per tile.
+    pub mines_per_tile: u8,
+    /// anti-mines placed on classic boards on top of their mines, see
+    /// `Tile::contains_anti_mine`. flags turn into anti-flags past the last
+    /// flag of a stack when there are any.
+    pub anti_mines: u64,
+}
+
+impl Default for BoardRules {
+    fn default() -> Self {
+        Self {
+            topology: Topology::default(),
+            wrap: false,
+            stencil: Stencil::default(),
+            shape: BoardShape::default(),
+            first_click: FirstClick::default(),
+            mines_per_tile: 1,
+            anti_mines: 0,
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::image::{CompressedImageFormats, Image, ImageSampler, ImageType};
+use bevy::render::render_asset::RenderAssetUsages;
+
+/// radius of the hole of a donut board, relative to the board.
+const DONUT_HOLE: f32 = 0.45;
+
+/// outline of a classic board. tiles outside of it are void: they hold no
+/// mines, are not drawn, and touch no other tile.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum BoardShape {
+    #[default]
+    Rectangle,
+    Heart,
+    /// a ring around a hole in the middle of the board.
+    Donut,
+    /// tiles read from a mask file, which also sets the size of the board.
+    Mask {
+        width: i64,
+        height: i64,
+        /// `height` rows of `width` cells, `true` where there is a tile.
+        cells: Vec<bool>,
+    },
+}
+
+impl BoardShape {
+    pub const PRESETS: [BoardShape; 3] = [Self::Rectangle, Self::Heart, Self::Donut];
+
+    /// the following preset. masks are only set from the settings file.
+    pub fn next(&self) -> Self {
+        let index = Self::PRESETS.iter().position(|shape| shape == self);
+        match index {
+            Some(index) => Self::PRESETS[(index + 1) % Self::PRESETS.len()].clone(),
+            None => Self::Rectangle,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rectangle => "rectangle",
+            Self::Heart => "heart",
+            Self::Donut => "donut",
+            Self::Mask { .. } => "mask",
+        }
+    }
+
+    /// a preset name, or the path of a mask: a PNG image where opaque dark
+    /// pixels are tiles, or a text file where `.` and spaces are void.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        if let Some(shape) = Self::PRESETS
+            .into_iter()
+            .find(|shape| shape.name() == value)
+        {
+            return Ok(shape);
+        }
+
+        let rows = match value.to_lowercase().ends_with(".png") {
+            true => read_png_mask(value)?,
+            false => read_text_mask(value)?,
+        };
+
+        let height = rows.len() as i64;
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0) as i64;
+        if !rows.iter().flatten().any(|&cell| cell) {
+            return Err(format!("mask `{value}` has no tiles"));
+        }
+
+        let cells = rows
+            .into_iter()
+            .flat_map(|mut row| {
+                row.resize(width as usize, false);
+                row
+            })
+            .collect();
+
+        Ok(Self::Mask {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// size of the board when the shape fixes it.
+    pub fn size(&self) -> Option<(i64, i64)> {
+        match self {
+            Self::Mask { width, height, .. } => Some((*width, *height)),
+            _ => None,
+        }
+    }
+
+    /// whether there is a tile at `col`, `row` on a board of `width` x `height` tiles.
+    pub fn contains(&self, col: i64, row: i64, width: i64, height: i64) -> bool {
+        // tile centers, from -1 to 1 across the board with y growing upwards
+        let x = ((col as f32 + 0.5) / width as f32) * 2.0 - 1.0;
+        let y = 1.0 - ((row as f32 + 0.5) / height as f32) * 2.0;
+
+        match self {
+            Self::Rectangle => true,
+            Self::Heart => {
+                // the heart curve spans about 2.3 across and 2.25 high
+                let (x, y) = (x * 1.15, y * 1.15 + 0.1);
+                (x * x + y * y - 1.0).powi(3) - x * x * y.powi(3) <= 0.0
+            }
+            Self::Donut => (DONUT_HOLE..=1.0).contains(&x.hypot(y)),
+            Self::Mask {
+                width,
+                height,
+                cells,
+            } => {
+                (0..*width).contains(&col)
+                    && (0..*height).contains(&row)
+                    && cells[(row * width + col) as usize]
+            }
+        }
+    }
+}
+
+fn read_text_mask(path: &str) -> Result<Vec<Vec<bool>>, String> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|error| format!("could not read {path}: {error}"))?;
+
+    Ok(contents
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|cell| cell != '.' && cell != ' ')
+                .collect()
+        })
+        .collect())
+}
+
+fn read_png_mask(path: &str) -> Result<Vec<Vec<bool>>, String> {
+    let bytes = std::fs::read(path).map_err(|error| format!("could not read {path}: {error}"))?;
+    let image = Image::from_buffer(
+        &bytes,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::default(),
+    )
+    .map_err(|error| format!("could not decode {path}: {error}"))?;
+
+    let tile = |col, row| {
+        image.get_color_at(col, row).is_ok_and(|color| {
+            let color = color.to_srgba();
+            let luminance = 0.299 * color.red + 0.587 * color.green + 0.114 * color.blue;
+            color.alpha >= 0.5 && luminance < 0.5
+        })
+    };
+
+    Ok((0..image.height())
+        .map(|row| (0..image.width()).map(|col| tile(col, row)).collect())
+        .collect())
+}
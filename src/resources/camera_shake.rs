@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// how hard the board camera is shaking, from `0` for still to `1`. decays over time.
+#[derive(Debug, Resource, Default)]
+pub struct CameraShake {
+    pub trauma: f32,
+}
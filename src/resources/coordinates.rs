@@ -1,47 +1,60 @@
-use bevy::{math::Vec2, prelude::{Component, Transform}};
+use bevy::{
+    math::{IVec2, Vec2, Vec3},
+    prelude::{Component, Transform},
+};
 
-use crate::{TILE_SIZE, WINDOW_HEIGHT, WINDOW_WIDTH};
+use super::Topology;
+use crate::CHUNK_SIZE;
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Component)]
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy, Component)]
 pub struct Coordinates {
     pub col: i64,
     pub row: i64,
+    /// depth on a layered board, always `0` on flat ones.
+    pub layer: i64,
 }
 
 impl Coordinates {
     pub fn new(col: i64, row: i64) -> Self {
-        Self { col, row }
+        Self { col, row, layer: 0 }
     }
 
-    pub fn manhattan_distance(&self, other: &Self) -> u64 {
-        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    pub fn with_layer(self, layer: i64) -> Self {
+        Self { layer, ..self }
     }
 
-    pub fn get_transform(&self, z: f32) -> Transform  {
-        let x = -WINDOW_WIDTH / 2.0 + (self.col as f32) * TILE_SIZE + TILE_SIZE * 0.5;
-        let y = WINDOW_HEIGHT / 2.0 - (self.row as f32) * TILE_SIZE - TILE_SIZE * 0.5;
-
-        Transform::from_xyz(x, y, z)
+    pub fn manhattan_distance(&self, other: &Self) -> u64 {
+        self.row.abs_diff(other.row)
+            + self.col.abs_diff(other.col)
+            + self.layer.abs_diff(other.layer)
     }
 
-    pub fn from_screen_position(position: Vec2) -> Self {
-        let col = (position.x / TILE_SIZE) as i64;
-        let row = (position.y / TILE_SIZE) as i64;
-        Self { col, row }
+    /// straight line distance between tile centers, in tiles.
+    pub fn distance(&self, other: &Self) -> f32 {
+        let cols = (self.col - other.col) as f32;
+        let rows = (self.row - other.row) as f32;
+        let layers = (self.layer - other.layer) as f32;
+        Vec3::new(cols, rows, layers).length()
     }
-}
 
-impl From<Transform> for Coordinates {
-    fn from(transform: Transform) -> Self {
-        let x = transform.translation.x;
-        let y = transform.translation.y;
+    /// center of the tile in world space, see `Topology::tile_center`.
+    /// every layer is drawn in the same place.
+    pub fn get_transform(&self, topology: Topology, z: f32) -> Transform {
+        Transform::from_translation(topology.tile_center(self).extend(z))
+    }
 
-        let col = (x - TILE_SIZE * 0.5 + WINDOW_WIDTH / 2.0) / TILE_SIZE;
-        let row = (y + TILE_SIZE * 0.5 - WINDOW_HEIGHT / 2.0) / TILE_SIZE;
+    /// chunk of `CHUNK_SIZE` x `CHUNK_SIZE` tiles holding these coordinates,
+    /// within their layer.
+    pub fn chunk(&self) -> IVec2 {
+        IVec2::new(
+            self.col.div_euclid(CHUNK_SIZE) as i32,
+            self.row.div_euclid(CHUNK_SIZE) as i32,
+        )
+    }
 
-        Self {
-            col: col as i64,
-            row: row as i64,
-        }
+    /// tile of the first layer containing `position`, in world space.
+    /// the inverse of `get_transform`.
+    pub fn from_world_position(position: Vec2, topology: Topology) -> Self {
+        topology.tile_at(position)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use super::Coordinates;
+
+/// input device that last moved the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorSource {
+    #[default]
+    Mouse,
+    Keyboard,
+    Gamepad,
+    Touch,
+}
+
+/// tile currently targeted by the player, drawn by the `Hover` entity.
+#[derive(Debug, Resource, Default)]
+pub struct Cursor {
+    pub coordinates: Coordinates,
+    pub source: CursorSource,
+    pub visible: bool,
+}
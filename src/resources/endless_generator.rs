@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use super::{BoardRules, Coordinates, Shade, Tile};
+use crate::CHUNK_SIZE;
+
+/// lowest density of an endless board. with fewer mines an opening may
+/// never end, and revealing it would generate chunks forever.
+const MIN_DENSITY: f64 = 0.12;
+
+/// places the mines of an endless board one chunk at a time.
+///
+/// every chunk draws its mines from a generator seeded with the board seed and
+/// the chunk coordinates, so the same chunk always holds the same mines no
+/// matter in which order chunks are generated.
+#[derive(Debug)]
+pub struct EndlessGenerator {
+    pub seed: u64,
+    /// fraction of tiles holding a mine.
+    pub density: f64,
+    /// first revealed tile, kept clear of mines. no mines are placed before it is known.
+    pub start: Option<Coordinates>,
+    pub rules: BoardRules,
+}
+
+impl EndlessGenerator {
+    pub fn new(seed: u64, density: f64, rules: BoardRules) -> Self {
+        Self {
+            seed,
+            density: density.clamp(MIN_DENSITY, 1.0),
+            start: None,
+            rules,
+        }
+    }
+
+    /// mine layout of a chunk, indexed like `chunk_index`.
+    fn chunk_mines(&self, chunk: IVec2) -> Vec<bool> {
+        let area = (CHUNK_SIZE * CHUNK_SIZE) as usize;
+        let mut mines = vec![false; area];
+
+        let Some(start) = self.start else {
+            return mines;
+        };
+
+        let mut rng = StdRng::seed_from_u64(chunk_seed(self.seed, chunk));
+        let mut cells: Vec<usize> = (0..area).collect();
+        cells.shuffle(&mut rng);
+
+        let mine_count = (self.density * area as f64).round() as usize;
+        let origin = chunk_origin(chunk);
+        let start_neighbors: Vec<Coordinates> = self
+            .rules
+            .stencil
+            .offsets(self.rules.topology, start.row)
+            .iter()
+            .map(|&(col_offset, row_offset)| {
+                Coordinates::new(start.col + col_offset, start.row + row_offset)
+            })
+            .collect();
+
+        cells
+            .into_iter()
+            .filter(|&index| {
+                let coordinates = Coordinates::new(
+                    origin.col + index as i64 % CHUNK_SIZE,
+                    origin.row + index as i64 / CHUNK_SIZE,
+                );
+                // endless boards never wrap
+                !self.rules.first_click.keeps_clear(
+                    &coordinates,
+                    &start,
+                    &start_neighbors,
+                    self.rules.topology,
+                    None,
+                )
+            })
+            .take(mine_count)
+            .for_each(|index| mines[index] = true);
+
+        mines
+    }
+
+    /// builds the tiles of a chunk, numbered from the mines of the
+    /// surrounding chunks as well as its own.
+    pub fn generate_chunk(&self, chunk: IVec2) -> Vec<Tile> {
+        let mut layouts: HashMap<IVec2, Vec<bool>> = HashMap::new();
+        let mut is_mine = |coordinates: Coordinates| {
+            layouts
+                .entry(coordinates.chunk())
+                .or_insert_with_key(|&chunk| self.chunk_mines(chunk))[chunk_index(&coordinates)]
+        };
+
+        let origin = chunk_origin(chunk);
+        let mut tiles = Vec::with_capacity((CHUNK_SIZE * CHUNK_SIZE) as usize);
+
+        for local_row in 0..CHUNK_SIZE {
+            for local_col in 0..CHUNK_SIZE {
+                let coordinates = Coordinates::new(origin.col + local_col, origin.row + local_row);
+
+                let shade = Shade::from_coordinates(&coordinates, self.rules.topology);
+                let mut tile = Tile::new(shade);
+                if is_mine(coordinates) {
+                    tile.add_mine(1);
+                }
+
+                let number = self
+                    .rules
+                    .stencil
+                    .offsets(self.rules.topology, coordinates.row)
+                    .iter()
+                    .map(|&(col_offset, row_offset)| {
+                        Coordinates::new(coordinates.col + col_offset, coordinates.row + row_offset)
+                    })
+                    .filter(|&neighbor| is_mine(neighbor))
+                    .count();
+                tile.set_number(number as i32, number > 0);
+
+                tiles.push(tile);
+            }
+        }
+
+        tiles
+    }
+}
+
+/// top left tile of a chunk.
+fn chunk_origin(chunk: IVec2) -> Coordinates {
+    Coordinates::new(chunk.x as i64 * CHUNK_SIZE, chunk.y as i64 * CHUNK_SIZE)
+}
+
+/// position of a tile within its chunk, in reading order.
+pub(super) fn chunk_index(coordinates: &Coordinates) -> usize {
+    let local_row = coordinates.row.rem_euclid(CHUNK_SIZE);
+    let local_col = coordinates.col.rem_euclid(CHUNK_SIZE);
+    (local_row * CHUNK_SIZE + local_col) as usize
+}
+
+/// mixes the board seed with the chunk coordinates, splitmix64 style,
+/// so neighboring chunks get unrelated generators.
+fn chunk_seed(seed: u64, chunk: IVec2) -> u64 {
+    let packed = ((chunk.x as u32 as u64) << 32) | chunk.y as u32 as u64;
+    let mut hash = seed ^ packed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    hash ^ (hash >> 31)
+}
@@ -0,0 +1,117 @@
+use super::{Coordinates, Topology};
+
+/// tiles kept clear of mines around the first tile revealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirstClick {
+    /// only the revealed tile.
+    Classic,
+    /// the revealed tile and its neighbors, so the first click always opens up.
+    Opening,
+    /// tiles at most this many steps away, diagonals included.
+    Chebyshev(u64),
+    /// tiles at most this many steps away, without diagonals.
+    Manhattan(u64),
+    /// no tile, the first click may hit a mine.
+    None,
+}
+
+impl Default for FirstClick {
+    fn default() -> Self {
+        Self::Manhattan(3)
+    }
+}
+
+impl FirstClick {
+    pub const PRESETS: [FirstClick; 5] = [
+        Self::Classic,
+        Self::Opening,
+        Self::Chebyshev(2),
+        Self::Manhattan(3),
+        Self::None,
+    ];
+
+    /// the following preset. other radii are only set from the settings file.
+    pub fn next(self) -> Self {
+        let index = Self::PRESETS.iter().position(|&policy| policy == self);
+        match index {
+            Some(index) => Self::PRESETS[(index + 1) % Self::PRESETS.len()],
+            None => Self::Classic,
+        }
+    }
+
+    /// name of the policy, followed by its radius if it has one.
+    pub fn name(self) -> String {
+        match self {
+            Self::Classic => "classic".to_string(),
+            Self::Opening => "opening".to_string(),
+            Self::Chebyshev(radius) => format!("chebyshev {radius}"),
+            Self::Manhattan(radius) => format!("manhattan {radius}"),
+            Self::None => "none".to_string(),
+        }
+    }
+
+    /// a policy name, followed by a radius for `chebyshev` and `manhattan`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut words = value.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let radius = words
+            .next()
+            .map(|radius| {
+                radius
+                    .parse()
+                    .map_err(|_| format!("expected a radius, found `{radius}`"))
+            })
+            .transpose()?;
+
+        match (name, radius) {
+            ("classic", None) => Ok(Self::Classic),
+            ("opening", None) => Ok(Self::Opening),
+            ("chebyshev", Some(radius)) => Ok(Self::Chebyshev(radius)),
+            ("manhattan", Some(radius)) => Ok(Self::Manhattan(radius)),
+            ("none", None) => Ok(Self::None),
+            ("chebyshev" | "manhattan", None) => Err(format!("`{name}` needs a radius")),
+            _ => Err(format!("unknown first click policy `{value}`")),
+        }
+    }
+
+    /// whether `coordinates` must be kept clear of mines when the first tile
+    /// revealed is `start`, whose neighbors are `start_neighbors`. on boards
+    /// wrapping at `wrap` (width, height) the radius reaches across the edges.
+    pub fn keeps_clear(
+        self,
+        coordinates: &Coordinates,
+        start: &Coordinates,
+        start_neighbors: &[Coordinates],
+        topology: Topology,
+        wrap: Option<(i64, i64)>,
+    ) -> bool {
+        // the copy of `coordinates` closest to `start` once the board wraps
+        let nearest = match wrap {
+            Some((width, height)) => Coordinates::new(
+                start.col + wrapped_offset(coordinates.col - start.col, width),
+                start.row + wrapped_offset(coordinates.row - start.row, height),
+            )
+            .with_layer(coordinates.layer),
+            None => *coordinates,
+        };
+        let layers = coordinates.layer.abs_diff(start.layer);
+
+        match self {
+            Self::Classic => coordinates == start,
+            Self::Opening => coordinates == start || start_neighbors.contains(coordinates),
+            Self::Chebyshev(radius) => topology.steps(start, &nearest, true).max(layers) <= radius,
+            Self::Manhattan(radius) => topology.steps(start, &nearest, false) + layers <= radius,
+            Self::None => false,
+        }
+    }
+}
+
+/// `offset` along an axis of `size` tiles, taking the shorter way around.
+fn wrapped_offset(offset: i64, size: i64) -> i64 {
+    let offset = offset.rem_euclid(size);
+    if offset > size / 2 {
+        offset - size
+    } else {
+        offset
+    }
+}
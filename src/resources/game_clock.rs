@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// time spent on the current game, only advancing while it is ongoing.
+#[derive(Debug, Resource, Default)]
+pub struct GameClock {
+    pub elapsed: f32,
+}
@@ -1,13 +1,18 @@
 use bevy::prelude::*;
 
-#[derive(Debug, Resource, PartialEq)]
+#[derive(Debug, Resource, PartialEq, Default)]
 pub enum GameState {
+    #[default]
     Pending,
     Ongoing,
+    /// a mine was revealed, the board no longer takes input.
+    Lost,
+    /// every tile without a mine was revealed.
+    Won,
 }
 
-impl Default for GameState {
-    fn default() -> Self {
-        Self::Pending
+impl GameState {
+    pub fn is_over(&self) -> bool {
+        matches!(self, Self::Lost | Self::Won)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+/// side of a square atlas cell, in pixels.
+pub const ATLAS_CELL_SIZE: u32 = 64;
+pub const ATLAS_COLUMNS: u32 = 32;
+
+/// symbol that can be drawn over a tile from the glyph atlas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyph {
+    /// plain white cell, used to fill tiles with their vertex color.
+    Solid,
+    Digit(u8),
+    Flag,
+    Mine,
+    /// drawn over flags that turned out to be wrong once the game is lost.
+    Cross,
+    /// pointy topped hexagon filling its cell from top to bottom, used to fill hex tiles.
+    Hexagon,
+    /// sign of negative numbers.
+    Minus,
+    AntiMine,
+    AntiFlag,
+}
+
+impl Glyph {
+    pub fn index(self) -> u32 {
+        match self {
+            Self::Solid => 0,
+            Self::Digit(digit) => 1 + digit as u32,
+            Self::Flag => 11,
+            Self::Mine => 12,
+            Self::Cross => 13,
+            Self::Hexagon => 14,
+            Self::Minus => 15,
+            Self::AntiMine => 16,
+            Self::AntiFlag => 17,
+        }
+    }
+
+    /// area of the glyph in the atlas texture, in UV coordinates.
+    pub fn uv_rect(self) -> Rect {
+        let index = self.index() as f32;
+        let columns = ATLAS_COLUMNS as f32;
+        Rect::new(index / columns, 0.0, (index + 1.0) / columns, 1.0)
+    }
+}
+
+/// texture holding every `Glyph`, rendered once from the game font so tile
+/// numbers can be drawn as part of the chunk meshes.
+#[derive(Debug, Resource)]
+pub struct GlyphAtlas {
+    pub image: Handle<Image>,
+    pub material: Handle<ColorMaterial>,
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, DynamicVariant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Reveal,
+    Flag,
+    Unflag,
+    Chord,
+    NextUnrevealed,
+    NextFrontier,
+    PreviousLayer,
+    NextLayer,
+    CyclePalette,
+    ToggleShapeMarkers,
+    Menu,
+    ZoomIn,
+    ZoomOut,
+    FitToWindow,
+    NewGame,
+    Mute,
+    Autoplay,
+}
+
+impl Action {
+    pub const ALL: [Action; 21] = [
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Reveal,
+        Action::Flag,
+        Action::Unflag,
+        Action::Chord,
+        Action::NextUnrevealed,
+        Action::NextFrontier,
+        Action::PreviousLayer,
+        Action::NextLayer,
+        Action::CyclePalette,
+        Action::ToggleShapeMarkers,
+        Action::Menu,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::FitToWindow,
+        Action::NewGame,
+        Action::Mute,
+        Action::Autoplay,
+    ];
+
+    /// name used for this action in the settings file, e.g. `bind.next_frontier`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Reveal => "reveal",
+            Action::Flag => "flag",
+            Action::Unflag => "unflag",
+            Action::Chord => "chord",
+            Action::NextUnrevealed => "next_unrevealed",
+            Action::NextFrontier => "next_frontier",
+            Action::PreviousLayer => "previous_layer",
+            Action::NextLayer => "next_layer",
+            Action::CyclePalette => "cycle_palette",
+            Action::ToggleShapeMarkers => "toggle_shape_markers",
+            Action::Menu => "menu",
+            Action::ZoomIn => "zoom_in",
+            Action::ZoomOut => "zoom_out",
+            Action::FitToWindow => "fit_to_window",
+            Action::NewGame => "new_game",
+            Action::Mute => "mute",
+            Action::Autoplay => "autoplay",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    fn default_keys(self) -> Vec<KeyCode> {
+        match self {
+            Action::Up => vec![KeyCode::ArrowUp, KeyCode::KeyW, KeyCode::KeyK],
+            Action::Down => vec![KeyCode::ArrowDown, KeyCode::KeyS, KeyCode::KeyJ],
+            Action::Left => vec![KeyCode::ArrowLeft, KeyCode::KeyA, KeyCode::KeyH],
+            Action::Right => vec![KeyCode::ArrowRight, KeyCode::KeyD, KeyCode::KeyL],
+            Action::Reveal => vec![KeyCode::Space, KeyCode::Enter],
+            Action::Flag => vec![KeyCode::KeyF],
+            Action::Unflag => vec![KeyCode::KeyX],
+            Action::Chord => vec![KeyCode::KeyC],
+            Action::NextUnrevealed => vec![KeyCode::Tab],
+            Action::NextFrontier => vec![KeyCode::KeyG],
+            Action::PreviousLayer => vec![KeyCode::PageUp],
+            Action::NextLayer => vec![KeyCode::PageDown],
+            Action::CyclePalette => vec![KeyCode::F1],
+            Action::ToggleShapeMarkers => vec![KeyCode::F2],
+            Action::Menu => vec![KeyCode::Escape],
+            Action::ZoomIn => vec![KeyCode::Equal, KeyCode::NumpadAdd],
+            Action::ZoomOut => vec![KeyCode::Minus, KeyCode::NumpadSubtract],
+            Action::FitToWindow => vec![KeyCode::Digit0, KeyCode::Numpad0],
+            Action::NewGame => vec![KeyCode::KeyR],
+            Action::Mute => vec![KeyCode::KeyM],
+            Action::Autoplay => vec![KeyCode::KeyB],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct KeyBindings {
+    keys: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let keys = Action::ALL
+            .into_iter()
+            .map(|action| (action, action.default_keys()))
+            .collect();
+
+        Self { keys }
+    }
+}
+
+impl KeyBindings {
+    pub fn just_pressed(&self, action: Action, input: &ButtonInput<KeyCode>) -> bool {
+        input.any_just_pressed(self.keys[&action].iter().copied())
+    }
+
+    /// replaces the keys bound to `action_name` with a comma separated list of
+    /// key names, using bevy's `KeyCode` variant names (`KeyW`, `ArrowUp`, `Space`...).
+    pub fn bind(&mut self, action_name: &str, key_names: &str) -> Result<(), String> {
+        let action = Action::from_name(action_name)
+            .ok_or_else(|| format!("unknown action `{action_name}`"))?;
+
+        let keys = key_names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| parse_key(name).ok_or_else(|| format!("unknown key `{name}`")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.keys.insert(action, keys);
+        Ok(())
+    }
+}
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let variant = DynamicEnum::new(name, DynamicVariant::Unit);
+    KeyCode::from_reflect(&variant)
+}
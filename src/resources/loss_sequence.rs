@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+use super::Coordinates;
+
+/// progress of the animation played after a mine is hit.
+#[derive(Debug, Resource, Default)]
+pub struct LossSequence {
+    /// time since the mine was hit, in seconds.
+    pub elapsed: f32,
+    /// mines still hidden with the time they are revealed at, latest first.
+    pub ripple: Vec<(f32, Coordinates)>,
+}
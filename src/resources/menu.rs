@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+
+use super::Settings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuItem {
+    Resume,
+    NewGame,
+    Mode,
+    BoardWidth,
+    BoardHeight,
+    MineCount,
+    Topology,
+    Wrap,
+    Stencil,
+    Shape,
+    FirstClick,
+    MinesPerTile,
+    Layers,
+    Autoplay,
+    AutoplaySpeed,
+    Palette,
+    ShapeMarkers,
+    Animations,
+    LongPress,
+    MasterVolume,
+    SfxVolume,
+    MusicVolume,
+}
+
+impl MenuItem {
+    pub const ALL: [MenuItem; 22] = [
+        Self::Resume,
+        Self::NewGame,
+        Self::Mode,
+        Self::BoardWidth,
+        Self::BoardHeight,
+        Self::MineCount,
+        Self::Topology,
+        Self::Wrap,
+        Self::Stencil,
+        Self::Shape,
+        Self::FirstClick,
+        Self::MinesPerTile,
+        Self::Layers,
+        Self::Autoplay,
+        Self::AutoplaySpeed,
+        Self::Palette,
+        Self::ShapeMarkers,
+        Self::Animations,
+        Self::LongPress,
+        Self::MasterVolume,
+        Self::SfxVolume,
+        Self::MusicVolume,
+    ];
+
+    pub fn label(self, settings: &Settings) -> String {
+        match self {
+            Self::Resume => "Resume".to_string(),
+            Self::NewGame => "New game".to_string(),
+            Self::Mode => format!("Mode: {}", settings.mode.name()),
+            Self::BoardWidth => match settings.rules.shape.size() {
+                Some((width, _)) => format!("Width: {width} (mask)"),
+                None => format!("Width: {}", settings.board_width),
+            },
+            Self::BoardHeight => match settings.rules.shape.size() {
+                Some((_, height)) => format!("Height: {height} (mask)"),
+                None => format!("Height: {}", settings.board_height),
+            },
+            Self::MineCount => format!("Mines: {}", settings.mine_count),
+            Self::Topology => format!("Tiles: {}", settings.rules.topology.name()),
+            Self::Wrap => format!("Wrap edges: {}", on_off(settings.rules.wrap)),
+            Self::Stencil => format!("Neighbors: {}", settings.rules.stencil.name()),
+            Self::Shape => format!("Shape: {}", settings.rules.shape.name()),
+            Self::FirstClick => format!("First click: {}", settings.rules.first_click.name()),
+            Self::MinesPerTile => format!("Mines per tile: {}", settings.rules.mines_per_tile),
+            Self::Layers => format!("Layers: {}", settings.board_depth),
+            Self::Autoplay => format!("Autoplay: {}", on_off(settings.autoplay)),
+            Self::AutoplaySpeed => format!("Bot speed: {} moves/s", settings.autoplay_speed),
+            Self::Palette => format!("Palette: {}", settings.palette.name()),
+            Self::ShapeMarkers => format!("Shape markers: {}", on_off(settings.shape_markers)),
+            Self::Animations => format!("Animations: {}", on_off(settings.animations)),
+            Self::LongPress => format!("Long press: {:.2}s", settings.long_press_secs),
+            Self::MasterVolume => {
+                let muted = if settings.muted { " (muted)" } else { "" };
+                format!("Volume: {}{muted}", percent(settings.master_volume))
+            }
+            Self::SfxVolume => format!("Effects volume: {}", percent(settings.sfx_volume)),
+            Self::MusicVolume => format!("Music volume: {}", percent(settings.music_volume)),
+        }
+    }
+
+    /// applies the item, `step` being `-1` or `1` when cycling its value
+    /// and `0` when it is confirmed.
+    ///
+    /// returns `true` if the menu should close.
+    pub fn activate(self, settings: &mut Settings, step: i32) -> bool {
+        match self {
+            Self::Resume | Self::NewGame => return step == 0,
+            Self::Mode => settings.mode = settings.mode.next(),
+            Self::BoardWidth => step_size(&mut settings.board_width, step),
+            Self::BoardHeight => step_size(&mut settings.board_height, step),
+            Self::MineCount => {
+                let step = if step == 0 { 1 } else { step };
+                let mines = settings.mine_count as i64 + MENU_MINE_STEP * step as i64;
+                settings.mine_count = mines.max(1) as u64;
+            }
+            Self::Topology => settings.rules.topology = settings.rules.topology.next(),
+            Self::Wrap => settings.rules.wrap = !settings.rules.wrap,
+            Self::Stencil => settings.rules.stencil = settings.rules.stencil.next(),
+            Self::Shape => settings.rules.shape = settings.rules.shape.next(),
+            Self::FirstClick => settings.rules.first_click = settings.rules.first_click.next(),
+            Self::MinesPerTile => {
+                let step = if step == 0 { 1 } else { step };
+                let mines = settings.rules.mines_per_tile as i32 + step;
+                settings.rules.mines_per_tile = mines.clamp(1, MAX_MENU_MINES_PER_TILE) as u8;
+            }
+            Self::Layers => {
+                let step = if step == 0 { 1 } else { step };
+                settings.board_depth =
+                    (settings.board_depth + step as i64).clamp(1, MAX_MENU_LAYERS);
+            }
+            Self::Autoplay => settings.autoplay = !settings.autoplay,
+            Self::AutoplaySpeed => {
+                let step = if step == 0 { 1 } else { step };
+                let index = AUTOPLAY_SPEEDS
+                    .iter()
+                    .position(|&speed| speed >= settings.autoplay_speed)
+                    .unwrap_or(AUTOPLAY_SPEEDS.len() - 1) as i32;
+                let index = (index + step).clamp(0, AUTOPLAY_SPEEDS.len() as i32 - 1);
+                settings.autoplay_speed = AUTOPLAY_SPEEDS[index as usize];
+            }
+            Self::Palette => {
+                settings.palette = match step {
+                    -1 => settings.palette.previous(),
+                    _ => settings.palette.next(),
+                };
+            }
+            Self::ShapeMarkers => settings.shape_markers = !settings.shape_markers,
+            Self::Animations => settings.animations = !settings.animations,
+            Self::LongPress => {
+                let step = if step == 0 { 1 } else { step };
+                settings.long_press_secs =
+                    (settings.long_press_secs + 0.05 * step as f32).clamp(0.15, 1.5);
+            }
+            Self::MasterVolume => step_volume(&mut settings.master_volume, step),
+            Self::SfxVolume => step_volume(&mut settings.sfx_volume, step),
+            Self::MusicVolume => step_volume(&mut settings.music_volume, step),
+        }
+
+        false
+    }
+}
+
+/// largest board side the menu goes up to. the settings file allows more.
+const MAX_MENU_BOARD_SIZE: i64 = 100;
+/// mines added or removed per step of the menu.
+const MENU_MINE_STEP: i64 = 5;
+
+/// moves per second the bot can be set to from the menu.
+const AUTOPLAY_SPEEDS: [f32; 8] = [1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0];
+
+/// most mines per tile the menu goes up to. the settings file allows more.
+const MAX_MENU_MINES_PER_TILE: i32 = 5;
+
+/// most layers the menu goes up to. the settings file allows more.
+const MAX_MENU_LAYERS: i64 = 9;
+
+/// moves a board side by one tile, confirming counting as a step up.
+fn step_size(size: &mut i64, step: i32) {
+    let step = if step == 0 { 1 } else { step };
+    *size = (*size + step as i64).clamp(1, MAX_MENU_BOARD_SIZE);
+}
+
+/// moves a volume by a tenth, confirming counting as a step up.
+fn step_volume(volume: &mut f32, step: i32) {
+    let step = if step == 0 { 1 } else { step };
+    *volume = ((*volume * 10.0).round() + step as f32).clamp(0.0, 10.0) / 10.0;
+}
+
+fn percent(volume: f32) -> String {
+    format!("{}%", (volume * 100.0).round())
+}
+
+fn on_off(value: bool) -> &'static str {
+    match value {
+        true => "on",
+        false => "off",
+    }
+}
+
+#[derive(Debug, Resource, Default)]
+pub struct Menu {
+    pub open: bool,
+    pub selected: usize,
+    /// problem shown under the entries, until the menu closes.
+    pub error: Option<String>,
+}
+
+impl Menu {
+    /// opens the menu with `message` shown under the entries.
+    pub fn show_error(&mut self, message: String) {
+        self.open = true;
+        self.error = Some(message);
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.error = None;
+    }
+
+    pub fn select(&mut self, item: MenuItem) {
+        self.selected = MenuItem::ALL
+            .iter()
+            .position(|&entry| entry == item)
+            .unwrap_or_default();
+    }
+
+    pub fn move_selection(&mut self, step: i32) {
+        let count = MenuItem::ALL.len() as i32;
+        self.selected = (self.selected as i32 + step).rem_euclid(count) as usize;
+    }
+
+    pub fn selected_item(&self) -> MenuItem {
+        MenuItem::ALL[self.selected]
+    }
+}
@@ -3,4 +3,4 @@ use bevy::prelude::*;
 #[derive(Resource)]
 pub struct MeshHandles {
     pub tile_mesh: Handle<Mesh>,
-}
\ No newline at end of file
+}
@@ -1,15 +1,67 @@
+mod autoplay;
+mod board_mode;
+mod board_rules;
+mod board_shape;
+mod camera_shake;
 mod coordinates;
+mod cursor;
+mod endless_generator;
+mod first_click;
+mod game_clock;
 mod gamestate;
+mod glyph_atlas;
+mod key_bindings;
+mod loss_sequence;
+mod menu;
 mod mesh_handles;
+mod mouse_drag;
+mod palette;
+mod particle_pool;
+mod personal_bests;
+mod settings;
 mod shade;
+mod solver;
+mod sound_clip;
+mod sound_handles;
+mod stencil;
 mod tile;
+mod tile_animations;
 mod tile_material_handles;
 mod tilemap;
+mod topology;
+mod touch_press;
+mod win_sequence;
 
+pub use autoplay::Autoplay;
+pub use board_mode::BoardMode;
+pub use board_rules::BoardRules;
+pub use board_shape::BoardShape;
+pub use camera_shake::CameraShake;
 pub use coordinates::Coordinates;
+pub use cursor::{Cursor, CursorSource};
+pub use endless_generator::EndlessGenerator;
+pub use first_click::FirstClick;
+pub use game_clock::GameClock;
 pub use gamestate::GameState;
+pub use glyph_atlas::{Glyph, GlyphAtlas, ATLAS_CELL_SIZE, ATLAS_COLUMNS};
+pub use key_bindings::{Action, KeyBindings};
+pub use loss_sequence::LossSequence;
+pub use menu::{Menu, MenuItem};
 pub use mesh_handles::MeshHandles;
+pub use mouse_drag::MouseDrag;
+pub use palette::Palette;
+pub use particle_pool::ParticlePool;
+pub use personal_bests::PersonalBests;
+pub use settings::Settings;
 pub use shade::Shade;
+pub use solver::{solve, BotMove};
+pub use sound_clip::{SoundClip, SoundClipDecoder, SAMPLE_RATE};
+pub use sound_handles::SoundHandles;
+pub use stencil::Stencil;
 pub use tile::Tile;
+pub use tile_animations::{TileAnimation, TileAnimationKind, TileAnimations, TilePose};
 pub use tile_material_handles::TileMaterialHandles;
 pub use tilemap::TileMap;
+pub use topology::Topology;
+pub use touch_press::TouchPress;
+pub use win_sequence::WinSequence;
@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+/// left button press that pans the camera once it travels far enough.
+#[derive(Debug, Resource, Default)]
+pub struct MouseDrag {
+    /// last cursor position while the button is held, in window coordinates.
+    pub last: Option<Vec2>,
+    pub travelled: f32,
+    /// whether the current (or just released) press turned into a pan,
+    /// in which case it must not also reveal a tile.
+    pub dragging: bool,
+}
@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+
+use super::{Shade, Tile};
+
+/// color scheme used for numbers, mines and marks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Palette {
+    #[default]
+    Standard,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 4] = [
+        Self::Standard,
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::Tritanopia,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|&palette| palette == self)
+            .unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|&palette| palette == self)
+            .unwrap();
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Deuteranopia => "deuteranopia",
+            Self::Protanopia => "protanopia",
+            Self::Tritanopia => "tritanopia",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|palette| palette.name() == name)
+    }
+
+    pub fn number_color(self, number: usize) -> Color {
+        match self {
+            Self::Standard => match number {
+                1 => Color::srgb(10.0, 0.0, 0.0),
+                2 => Color::srgb(0.0, 10.0, 0.0),
+                3 => Color::srgb(0.0, 0.0, 10.0),
+                4 => Color::srgb(7.5, 0.0, 7.5),
+                5 => Color::srgb(0.0, 7.5, 7.5),
+                6 => Color::srgb(7.5, 7.5, 0.0),
+                7 => Color::srgb(7.5, 3.5, 3.5),
+                8 => Color::srgb(4.0, 6.0, 8.5),
+                _ => extended_number_color(number),
+            },
+            // Okabe-Ito colors, distinguishable without red-green discrimination
+            Self::Deuteranopia => match number {
+                1 => glow(0, 114, 178),
+                2 => glow(230, 159, 0),
+                3 => glow(86, 180, 233),
+                4 => glow(213, 94, 0),
+                5 => glow(240, 228, 66),
+                6 => glow(204, 121, 167),
+                7 => glow(0, 158, 115),
+                8 => glow(200, 200, 200),
+                _ => extended_number_color(number),
+            },
+            // reds look dim to protans, so every color keeps a bright green or blue part
+            Self::Protanopia => match number {
+                1 => glow(70, 150, 255),
+                2 => glow(240, 228, 66),
+                3 => glow(150, 220, 255),
+                4 => glow(255, 170, 0),
+                5 => glow(170, 120, 255),
+                6 => glow(0, 200, 200),
+                7 => glow(255, 240, 180),
+                8 => glow(200, 200, 200),
+                _ => extended_number_color(number),
+            },
+            // avoids pairs that collapse along the blue-yellow axis
+            Self::Tritanopia => match number {
+                1 => glow(220, 50, 47),
+                2 => glow(0, 160, 160),
+                3 => glow(255, 140, 170),
+                4 => glow(120, 200, 255),
+                5 => glow(160, 30, 90),
+                6 => glow(80, 220, 200),
+                7 => glow(255, 110, 80),
+                8 => glow(200, 200, 200),
+                _ => extended_number_color(number),
+            },
+        }
+    }
+
+    pub fn mine_color(self) -> Color {
+        match self {
+            Self::Standard => Color::srgb_u8(245, 42, 32),
+            Self::Deuteranopia => Color::srgb_u8(213, 94, 0),
+            Self::Protanopia => Color::srgb_u8(255, 176, 0),
+            Self::Tritanopia => Color::srgb_u8(220, 50, 47),
+        }
+    }
+
+    pub fn anti_mine_color(self) -> Color {
+        match self {
+            Self::Standard => Color::srgb_u8(60, 120, 255),
+            Self::Deuteranopia | Self::Protanopia => Color::srgb_u8(86, 180, 233),
+            Self::Tritanopia => Color::srgb_u8(0, 160, 160),
+        }
+    }
+
+    pub fn anti_mark_color(self) -> Color {
+        match self {
+            Self::Standard => Color::srgb(3.0, 0.0, 4.5),
+            Self::Deuteranopia => Color::srgb(3.2, 1.6, 0.0),
+            Self::Protanopia => Color::srgb(3.2, 2.8, 0.0),
+            Self::Tritanopia => Color::srgb(3.0, 0.5, 1.5),
+        }
+    }
+
+    /// color of a zero hiding mines and anti-mines that cancel out.
+    pub fn zero_color(self) -> Color {
+        Color::srgb(3.0, 3.0, 3.0)
+    }
+
+    pub fn mark_color(self) -> Color {
+        match self {
+            Self::Standard => Color::srgb(0.0, 4.5, 0.0),
+            Self::Deuteranopia | Self::Protanopia => Color::srgb(0.0, 1.6, 3.2),
+            Self::Tritanopia => Color::srgb(0.0, 2.5, 2.5),
+        }
+    }
+}
+
+impl Palette {
+    /// fill color of a tile. when `shape_markers` is set, mines and marks keep
+    /// their plain tile fill and are told apart by the glyph drawn on top instead.
+    pub fn tile_color(self, tile: &Tile, shape_markers: bool) -> Color {
+        if !shape_markers {
+            if tile.contains_anti_mine() && tile.is_flipped() {
+                return self.anti_mine_color();
+            }
+
+            if tile.contains_mine() && tile.is_flipped() {
+                return self.mine_color();
+            }
+
+            if tile.has_anti_flag() {
+                return self.anti_mark_color();
+            }
+
+            if tile.is_marked() {
+                return self.mark_color();
+            }
+        }
+
+        match (tile.is_flipped(), tile.shade()) {
+            (false, Shade::Light) => Color::srgb_u8(10, 10, 10),
+            (false, Shade::Medium) => Color::srgb_u8(5, 5, 5),
+            (false, Shade::Dark) => Color::srgb_u8(0, 0, 0),
+            (true, Shade::Light) => Color::srgb_u8(50, 50, 50),
+            (true, Shade::Medium) => Color::srgb_u8(45, 45, 45),
+            (true, Shade::Dark) => Color::srgb_u8(40, 40, 40),
+        }
+    }
+}
+
+/// color of numbers past the classic eight, reached with large stencils and
+/// several mines per tile. hues are a golden angle apart, so numbers close to
+/// each other look different.
+fn extended_number_color(number: usize) -> Color {
+    let hue = (number as f32 * 137.5) % 360.0;
+    let color = Color::hsl(hue, 0.9, 0.6).to_srgba();
+    Color::srgb(color.red * 7.5, color.green * 7.5, color.blue * 7.5)
+}
+
+/// scales an sRGB color into HDR range so numbers glow like the standard palette.
+fn glow(r: u8, g: u8, b: u8) -> Color {
+    let scale = 7.5 / 255.0;
+    Color::srgb(r as f32 * scale, g as f32 * scale, b as f32 * scale)
+}
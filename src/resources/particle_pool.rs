@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// particle entities spawned once at startup and reused by every effect,
+/// so effects never spawn or despawn entities while playing.
+#[derive(Debug, Resource, Default)]
+pub struct ParticlePool {
+    /// hidden particles free to be reused.
+    pub free: Vec<Entity>,
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::{BoardShape, FirstClick, Stencil, TileMap, Topology};
+
+/// fastest clear of every board size played, kept in a file of
+/// `<width>x<height>x<mines> = <seconds>` lines.
+#[derive(Debug, Resource, Default)]
+pub struct PersonalBests {
+    times: HashMap<String, f32>,
+}
+
+impl PersonalBests {
+    /// reads the records file, starting empty when there is none.
+    /// lines that do not parse are skipped.
+    pub fn load(path: &str) -> Self {
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+
+        let times = contents
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .filter_map(|(board, time)| Some((board.trim().to_string(), time.trim().parse().ok()?)))
+            .collect();
+
+        Self { times }
+    }
+
+    pub fn save(&self, path: &str) {
+        let mut boards: Vec<_> = self.times.iter().collect();
+        boards.sort_by(|a, b| a.0.cmp(b.0));
+
+        let contents: String = boards
+            .into_iter()
+            .map(|(board, time)| format!("{board} = {time}\n"))
+            .collect();
+
+        if let Err(error) = std::fs::write(path, contents) {
+            warn!("could not save records to {path}: {error}");
+        }
+    }
+
+    /// plain square boards keep the bare size as their key, so records from
+    /// before other variants existed still apply.
+    pub fn board_key(tilemap: &TileMap) -> String {
+        let mut key = format!(
+            "{}x{}x{}",
+            tilemap.width, tilemap.height, tilemap.mine_count
+        );
+        if tilemap.depth > 1 {
+            key += &format!("-d{}", tilemap.depth);
+        }
+        if tilemap.rules.topology != Topology::Square {
+            key += &format!("-{}", tilemap.rules.topology.name());
+        }
+        match &tilemap.rules.shape {
+            BoardShape::Rectangle => {}
+            // masks keep apart by their cells, hashed so the key stays short
+            BoardShape::Mask {
+                width,
+                height,
+                cells,
+            } => key += &format!("-mask{width}x{height}-{:016x}", cells_hash(cells)),
+            shape => key += &format!("-{}", shape.name()),
+        }
+        match &tilemap.rules.stencil {
+            Stencil::Standard => {}
+            // custom stencils keep apart by their offsets, in any order
+            Stencil::Custom(offsets) => {
+                let mut offsets = offsets.clone();
+                offsets.sort();
+                key += "-custom";
+                for (col, row) in offsets {
+                    key += &format!("({col},{row})");
+                }
+            }
+            stencil => key += &format!("-{}", stencil.name()),
+        }
+        if tilemap.rules.first_click != FirstClick::default() {
+            let policy = tilemap.rules.first_click.name().replace(' ', "");
+            key += &format!("-{policy}");
+        }
+        if tilemap.rules.mines_per_tile > 1 {
+            key += &format!("-x{}", tilemap.rules.mines_per_tile);
+        }
+        if tilemap.rules.anti_mines > 0 {
+            key += &format!("-a{}", tilemap.rules.anti_mines);
+        }
+        if tilemap.rules.wrap {
+            key += "-wrap";
+        }
+
+        key
+    }
+
+    pub fn get(&self, board: &str) -> Option<f32> {
+        self.times.get(board).copied()
+    }
+
+    /// stores `time` if it beats the record of the board.
+    ///
+    /// returns `true` if it is a new record.
+    pub fn record(&mut self, board: &str, time: f32) -> bool {
+        if self.get(board).is_some_and(|best| best <= time) {
+            return false;
+        }
+
+        self.times.insert(board.to_string(), time);
+        true
+    }
+}
+
+/// FNV-1a hash of the cells of a mask, the same on every build so keys
+/// written to the records file keep matching.
+fn cells_hash(cells: &[bool]) -> u64 {
+    cells.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &cell| {
+        (hash ^ cell as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
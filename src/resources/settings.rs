@@ -0,0 +1,272 @@
+use bevy::prelude::*;
+
+use super::{
+    BoardMode, BoardRules, BoardShape, FirstClick, KeyBindings, Palette, Stencil, TileMap, Topology,
+};
+use crate::{COLS, MINE_COUNT, ROWS};
+
+#[derive(Debug, Resource)]
+pub struct Settings {
+    pub palette: Palette,
+    /// draw flags and mines as distinct shapes instead of solid color fills.
+    pub shape_markers: bool,
+    /// tween tile flips and flags. off shows every change at once.
+    pub animations: bool,
+    pub key_bindings: KeyBindings,
+    /// how long a touch must be held to flag a tile, in seconds.
+    pub long_press_secs: f32,
+    pub board_width: i64,
+    pub board_height: i64,
+    /// layers of a classic board, `1` for a flat one.
+    pub board_depth: i64,
+    pub mine_count: u64,
+    /// scales every sound, from `0` to `1`.
+    pub master_volume: f32,
+    pub sfx_volume: f32,
+    pub music_volume: f32,
+    /// silences everything without losing the volume levels.
+    pub muted: bool,
+    /// board used by the next game.
+    pub mode: BoardMode,
+    /// rules of the next game.
+    pub rules: BoardRules,
+    /// let the bot play the current board, see `solve`.
+    pub autoplay: bool,
+    /// moves the bot plays per second.
+    pub autoplay_speed: f32,
+    /// fraction of tiles holding a mine in endless mode.
+    pub endless_density: f64,
+    /// seed of endless boards, a random one for every game when unset.
+    pub seed: Option<u64>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            palette: Palette::default(),
+            shape_markers: false,
+            animations: true,
+            key_bindings: KeyBindings::default(),
+            long_press_secs: 0.4,
+            board_width: COLS,
+            board_height: ROWS,
+            board_depth: 1,
+            mine_count: MINE_COUNT,
+            master_volume: 1.0,
+            sfx_volume: 0.8,
+            music_volume: 0.5,
+            muted: false,
+            mode: BoardMode::default(),
+            rules: BoardRules::default(),
+            autoplay: false,
+            autoplay_speed: 8.0,
+            endless_density: 0.16,
+            seed: None,
+        }
+    }
+}
+
+impl Settings {
+    /// fresh board for a new game, as described by these settings.
+    pub fn new_tilemap(&self) -> TileMap {
+        match self.mode {
+            BoardMode::Classic => {
+                // masks come with their own size
+                let (width, height) = self
+                    .rules
+                    .shape
+                    .size()
+                    .unwrap_or((self.board_width, self.board_height));
+                TileMap::new(
+                    width,
+                    height,
+                    self.board_depth,
+                    self.mine_count,
+                    self.rules.clone(),
+                )
+            }
+            BoardMode::Endless => {
+                let seed = self.seed.unwrap_or_else(rand::random);
+                TileMap::new_endless(seed, self.endless_density, self.rules.clone())
+            }
+        }
+    }
+
+    /// reads settings from a file of `key = value` lines, falling back to the
+    /// defaults for anything missing. `#` starts a comment.
+    ///
+    /// invalid lines are reported and skipped rather than aborting the load.
+    pub fn load(path: &str) -> Self {
+        let mut settings = Self::default();
+
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return settings;
+        };
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let result = match line.split_once('=') {
+                Some((key, value)) => settings.set(key.trim(), value.trim()),
+                None => Err(format!("expected `key = value`, found `{line}`")),
+            };
+
+            if let Err(message) = result {
+                warn!("{path}:{}: {message}", index + 1);
+            }
+        }
+
+        if let Err(message) = settings.check_mine_count() {
+            warn!("{path}: {message}");
+        }
+
+        settings
+    }
+
+    /// volume sound effects play at, after the master volume and muting.
+    pub fn effective_sfx_volume(&self) -> f32 {
+        match self.muted {
+            true => 0.0,
+            false => self.master_volume * self.sfx_volume,
+        }
+    }
+
+    pub fn effective_music_volume(&self) -> f32 {
+        match self.muted {
+            true => 0.0,
+            false => self.master_volume * self.music_volume,
+        }
+    }
+
+    /// falls back to a mine count that fits when the one read leaves no tile
+    /// free on the board. masks and the first click policy are checked once
+    /// the mines are placed, see `TileMap::generate_mines`.
+    fn check_mine_count(&mut self) -> Result<(), String> {
+        let tiles = self
+            .board_width
+            .saturating_mul(self.board_height)
+            .saturating_mul(self.board_depth) as u64;
+        let room = (tiles - 1).saturating_mul(self.rules.mines_per_tile as u64);
+        if self.mine_count <= room {
+            return Ok(());
+        }
+
+        let message = format!(
+            "{} mines do not fit on the board, it holds {room} at most",
+            self.mine_count
+        );
+        self.mine_count = MINE_COUNT.min(room);
+        Err(message)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "palette" => {
+                self.palette = Palette::from_name(value)
+                    .ok_or_else(|| format!("unknown palette `{value}`"))?;
+            }
+            "shape_markers" => self.shape_markers = parse_bool(value)?,
+            "animations" => self.animations = parse_bool(value)?,
+            "long_press_secs" => {
+                let secs: f32 = parse_number(value)?;
+                if !secs.is_finite() || secs <= 0.0 {
+                    return Err("a long press must last some time".to_string());
+                }
+                self.long_press_secs = secs;
+            }
+            "board_width" => self.board_width = parse_size(value)?,
+            "board_height" => self.board_height = parse_size(value)?,
+            "board_depth" => {
+                let depth = parse_number(value)?;
+                if depth < 1 {
+                    return Err("a board must have at least one layer".to_string());
+                }
+                self.board_depth = depth;
+            }
+            "mine_count" => self.mine_count = parse_number(value)?,
+            "master_volume" => self.master_volume = parse_volume(value)?,
+            "sfx_volume" => self.sfx_volume = parse_volume(value)?,
+            "music_volume" => self.music_volume = parse_volume(value)?,
+            "muted" => self.muted = parse_bool(value)?,
+            "mode" => {
+                self.mode = BoardMode::from_name(value)
+                    .ok_or_else(|| format!("unknown board mode `{value}`"))?;
+            }
+            "topology" => {
+                self.rules.topology = Topology::from_name(value)
+                    .ok_or_else(|| format!("unknown topology `{value}`"))?;
+            }
+            "wrap" => self.rules.wrap = parse_bool(value)?,
+            "stencil" => self.rules.stencil = Stencil::parse(value)?,
+            "shape" => self.rules.shape = BoardShape::parse(value)?,
+            "first_click" => self.rules.first_click = FirstClick::parse(value)?,
+            "anti_mines" => self.rules.anti_mines = parse_number(value)?,
+            "mines_per_tile" => {
+                let mines_per_tile = parse_number(value)?;
+                if mines_per_tile == 0 {
+                    return Err("a tile must be able to hold at least one mine".to_string());
+                }
+                self.rules.mines_per_tile = mines_per_tile;
+            }
+            "autoplay" => self.autoplay = parse_bool(value)?,
+            "autoplay_speed" => {
+                let speed: f32 = parse_number(value)?;
+                if !speed.is_finite() || speed <= 0.0 {
+                    return Err("the bot must play at least some moves per second".to_string());
+                }
+                self.autoplay_speed = speed;
+            }
+            "endless_density" => {
+                let density: f64 = parse_number(value)?;
+                if density.is_nan() || density <= 0.0 || density > 1.0 {
+                    return Err(format!(
+                        "expected a density above 0 and at most 1, found `{value}`"
+                    ));
+                }
+                self.endless_density = density;
+            }
+            "seed" => self.seed = Some(parse_number(value)?),
+            _ => match key.strip_prefix("bind.") {
+                Some(action) => self.key_bindings.bind(action, value)?,
+                None => return Err(format!("unknown setting `{key}`")),
+            },
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected `true` or `false`, found `{value}`"))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("expected a number, found `{value}`"))
+}
+
+fn parse_size(value: &str) -> Result<i64, String> {
+    let size = parse_number(value)?;
+    if size < 1 {
+        return Err(format!("expected a size of at least 1, found `{value}`"));
+    }
+
+    Ok(size)
+}
+
+fn parse_volume(value: &str) -> Result<f32, String> {
+    let volume: f32 = parse_number(value)?;
+    if !(0.0..=1.0).contains(&volume) {
+        return Err(format!(
+            "expected a volume between 0 and 1, found `{value}`"
+        ));
+    }
+
+    Ok(volume)
+}
@@ -1,15 +1,15 @@
+use super::{Coordinates, Topology};
+
 #[derive(Debug, Clone, Copy)]
 pub enum Shade {
     Light,
     Dark,
+    /// third shade, only needed by hex boards.
+    Medium,
 }
 
 impl Shade {
-    pub fn from_coordinates(row: i64, col: i64) -> Self {
-        match (row + col) % 2 {
-            0 => Self::Light,
-            1 => Self::Dark,
-            _ => unreachable!(),
-        }
+    pub fn from_coordinates(coordinates: &Coordinates, topology: Topology) -> Self {
+        topology.shade(coordinates)
     }
 }
@@ -0,0 +1,524 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+
+use super::{Coordinates, TileMap};
+
+/// most tiles assigned while searching a single group of frontier tiles.
+/// past it, the solver settles for rough odds on that group.
+const SEARCH_BUDGET: usize = 200_000;
+
+/// a move of the autoplay bot, see `solve`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotMove {
+    /// a tile known to hold no mine, or the first tile of a game.
+    Reveal(Coordinates),
+    /// a tile known to add `charge` to the numbers around it, `-1` for an anti-mine.
+    Mark(Coordinates, i32),
+    /// the tile least likely to hold a mine, when nothing is certain.
+    Guess(Coordinates),
+}
+
+impl BotMove {
+    pub fn coordinates(self) -> Coordinates {
+        match self {
+            Self::Reveal(coordinates) | Self::Mark(coordinates, _) | Self::Guess(coordinates) => {
+                coordinates
+            }
+        }
+    }
+}
+
+/// the moves to play next on `tilemap`: every move the revealed numbers prove
+/// safe, or a single guess when they prove nothing. marks already on the
+/// board are trusted. returns nothing once every tile is revealed or marked.
+pub fn solve(tilemap: &TileMap) -> Vec<BotMove> {
+    if !tilemap.iter().any(|(_, tile)| tile.is_flipped()) {
+        return first_move(tilemap)
+            .map(BotMove::Reveal)
+            .into_iter()
+            .collect();
+    }
+
+    let frontier = Frontier::new(tilemap);
+    let odds = frontier.odds();
+
+    let mut moves = Vec::new();
+    for (index, odds) in odds.iter().enumerate() {
+        let coordinates = frontier.tiles[index];
+        match odds {
+            Odds::Known(0) => moves.push(BotMove::Reveal(coordinates)),
+            &Odds::Known(charge) => moves.push(BotMove::Mark(coordinates, charge)),
+            Odds::Likely { .. } => {}
+        }
+    }
+
+    if !moves.is_empty() {
+        return moves;
+    }
+
+    frontier
+        .guess(tilemap, &odds)
+        .map(BotMove::Guess)
+        .into_iter()
+        .collect()
+}
+
+/// the middle tile of the shown layer, or the closest tile to it on boards
+/// with holes in the middle.
+fn first_move(tilemap: &TileMap) -> Option<Coordinates> {
+    if tilemap.is_endless() {
+        return Some(Coordinates::default());
+    }
+
+    let middle =
+        Coordinates::new(tilemap.width / 2, tilemap.height / 2).with_layer(tilemap.view_layer);
+    tilemap
+        .iter()
+        .map(|(coordinates, _)| coordinates)
+        .filter(|coordinates| coordinates.layer == middle.layer)
+        .min_by(|a, b| a.distance(&middle).total_cmp(&b.distance(&middle)))
+}
+
+/// what the solver knows about a hidden tile.
+#[derive(Debug, Clone, Copy)]
+enum Odds {
+    /// the tile adds this much to the numbers around it in every layout
+    /// of the mines that matches the board.
+    Known(i32),
+    /// chance of the tile holding a mine of either kind, and how many it
+    /// is expected to hold.
+    Likely { mine: f64, mines: f64 },
+}
+
+/// a revealed number: the hidden tiles around it add up to `sum`.
+struct Constraint {
+    tiles: Vec<usize>,
+    sum: i32,
+    /// whether one of the tiles must hold a mine, as the number shows even
+    /// when the mines around it cancel out.
+    needs_mine: bool,
+}
+
+/// hidden tiles next to a revealed one, with what the numbers say about them.
+struct Frontier {
+    tiles: Vec<Coordinates>,
+    /// charges each tile may add, narrowed to `0` next to an empty tile.
+    domains: Vec<RangeInclusive<i32>>,
+    constraints: Vec<Constraint>,
+    /// constraints each tile takes part in.
+    links: Vec<Vec<usize>>,
+    /// charges any hidden tile may add.
+    charges: RangeInclusive<i32>,
+}
+
+impl Frontier {
+    fn new(tilemap: &TileMap) -> Self {
+        let most = match tilemap.is_endless() {
+            true => 1,
+            false => tilemap.rules.mines_per_tile as i32,
+        };
+        let least = -((tilemap.rules.anti_mines > 0 && !tilemap.is_endless()) as i32);
+
+        let mut frontier = Self {
+            tiles: Vec::new(),
+            domains: Vec::new(),
+            constraints: Vec::new(),
+            links: Vec::new(),
+            charges: least..=most,
+        };
+        let mut indices = HashMap::new();
+
+        for (coordinates, tile) in tilemap.iter() {
+            if !tile.is_flipped() {
+                continue;
+            }
+
+            let neighbors = tilemap.get_neighbors(&coordinates);
+            let hidden: Vec<Coordinates> = neighbors
+                .iter()
+                .copied()
+                .filter(|&neighbor| tilemap.contains(&neighbor))
+                .filter(|&neighbor| tilemap[neighbor].is_hoverable())
+                .collect();
+            if hidden.is_empty() {
+                continue;
+            }
+
+            let tiles = hidden
+                .into_iter()
+                .map(|neighbor| {
+                    *indices.entry(neighbor).or_insert_with(|| {
+                        frontier.tiles.push(neighbor);
+                        frontier.domains.push(frontier.charges.clone());
+                        frontier.links.push(Vec::new());
+                        frontier.tiles.len() - 1
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let Some(number) = tile.number() else {
+                // nothing around an empty tile holds a mine of either kind
+                for &index in &tiles {
+                    frontier.domains[index] = 0..=0;
+                }
+                continue;
+            };
+
+            let marked = neighbors
+                .iter()
+                .filter(|&neighbor| tilemap.contains(neighbor))
+                .map(|&neighbor| &tilemap[neighbor])
+                .filter(|neighbor| !neighbor.is_flipped() && neighbor.is_marked());
+            let (marked_charge, marked_mine) = marked.fold((0, false), |(charge, _), neighbor| {
+                (charge + neighbor.marked_charge(), true)
+            });
+
+            for &index in &tiles {
+                frontier.links[index].push(frontier.constraints.len());
+            }
+            frontier.constraints.push(Constraint {
+                tiles,
+                sum: number - marked_charge,
+                needs_mine: !marked_mine,
+            });
+        }
+
+        frontier
+    }
+
+    /// odds of every frontier tile, searching the layouts of each group of
+    /// tiles tied together by the numbers.
+    fn odds(&self) -> Vec<Odds> {
+        let mut odds = vec![Odds::Known(0); self.tiles.len()];
+        let mut grouped = vec![false; self.tiles.len()];
+        // searches leave every constraint as they found it
+        let mut sums: Vec<(i32, usize, usize)> = self
+            .constraints
+            .iter()
+            .map(|constraint| (0, constraint.tiles.len(), 0))
+            .collect();
+
+        for start in 0..self.tiles.len() {
+            if grouped[start] {
+                continue;
+            }
+
+            // tiles in the order they are reached, so the numbers they share
+            // are filled in early and cut the search short
+            let mut group = vec![start];
+            grouped[start] = true;
+            let mut next = 0;
+            while let Some(&index) = group.get(next) {
+                next += 1;
+                for &constraint in &self.links[index] {
+                    for &tile in &self.constraints[constraint].tiles {
+                        if !grouped[tile] {
+                            grouped[tile] = true;
+                            group.push(tile);
+                        }
+                    }
+                }
+            }
+
+            let mut search = Search::new(self, &group, &mut sums);
+            match search.run(0) && search.layouts > 0 {
+                true => {
+                    for (position, &tile) in group.iter().enumerate() {
+                        odds[tile] = search.odds(position);
+                    }
+                }
+                false => {
+                    for &tile in &group {
+                        odds[tile] = self.estimate(tile);
+                    }
+                }
+            }
+        }
+
+        odds
+    }
+
+    /// rough odds of a tile from the densest number around it, for groups too
+    /// large to search.
+    fn estimate(&self, tile: usize) -> Odds {
+        if self.domains[tile] == (0..=0) {
+            return Odds::Known(0);
+        }
+
+        let mine = self.links[tile]
+            .iter()
+            .map(|&constraint| {
+                let constraint = &self.constraints[constraint];
+                constraint.sum.max(0) as f64 / constraint.tiles.len() as f64
+            })
+            .fold(0.0, f64::max)
+            .min(1.0);
+        Odds::Likely { mine, mines: mine }
+    }
+
+    /// the hidden tile least likely to hold a mine, the frontier winning ties
+    /// since revealing it tells more.
+    fn guess(&self, tilemap: &TileMap, odds: &[Odds]) -> Option<Coordinates> {
+        let frontier = odds.iter().enumerate().map(|(index, odds)| match *odds {
+            Odds::Known(charge) => (index, (charge != 0) as u8 as f64, charge.max(0) as f64),
+            Odds::Likely { mine, mines } => (index, mine, mines),
+        });
+
+        let expected: f64 = frontier.clone().map(|(_, _, mines)| mines).sum();
+        let safest = frontier.min_by(|a, b| a.1.total_cmp(&b.1));
+
+        let on_frontier: HashSet<Coordinates> = self.tiles.iter().copied().collect();
+        let inside: Vec<Coordinates> = tilemap
+            .iter()
+            .filter(|(coordinates, tile)| tile.is_hoverable() && !on_frontier.contains(coordinates))
+            .map(|(coordinates, _)| coordinates)
+            .collect();
+
+        let inside_odds = match tilemap.is_endless() {
+            true => tilemap.density(),
+            false => {
+                let left = tilemap.mine_count as f64 - tilemap.marked_count() as f64 - expected;
+                (left.max(0.0) / inside.len().max(1) as f64).min(1.0)
+            }
+        };
+
+        match safest {
+            Some((index, mine, _)) if inside.is_empty() || mine <= inside_odds => {
+                Some(self.tiles[index])
+            }
+            // tiles with the fewest neighbors are the likeliest to open up
+            _ => inside
+                .into_iter()
+                .min_by_key(|coordinates| tilemap.get_neighbors(coordinates).len()),
+        }
+    }
+}
+
+/// depth first search through the layouts of the mines over a group of
+/// frontier tiles, tallying what each tile holds in them.
+struct Search<'a> {
+    frontier: &'a Frontier,
+    group: &'a [usize],
+    /// per constraint: charge so far, tiles left to assign, and tiles holding a mine.
+    sums: &'a mut [(i32, usize, usize)],
+    charges: Vec<i32>,
+    layouts: u64,
+    /// per tile of the group: layouts with a mine of either kind on it, mines
+    /// summed over all layouts, and the charge it holds if it is always the same.
+    mines: Vec<u64>,
+    mine_sums: Vec<f64>,
+    same: Vec<Option<Option<i32>>>,
+    budget: usize,
+}
+
+impl<'a> Search<'a> {
+    fn new(
+        frontier: &'a Frontier,
+        group: &'a [usize],
+        sums: &'a mut [(i32, usize, usize)],
+    ) -> Self {
+        Self {
+            frontier,
+            group,
+            sums,
+            charges: vec![0; group.len()],
+            layouts: 0,
+            mines: vec![0; group.len()],
+            mine_sums: vec![0.0; group.len()],
+            same: vec![None; group.len()],
+            budget: SEARCH_BUDGET,
+        }
+    }
+
+    /// assigns the tiles from `position` on. returns `false` when the budget
+    /// runs out.
+    fn run(&mut self, position: usize) -> bool {
+        if position == self.group.len() {
+            self.tally();
+            return true;
+        }
+
+        let tile = self.group[position];
+        for charge in self.frontier.domains[tile].clone() {
+            if self.budget == 0 {
+                return false;
+            }
+            self.budget -= 1;
+
+            self.charges[position] = charge;
+            let fits = self.assign(tile, charge);
+            let finished = !fits || self.run(position + 1);
+            self.unassign(tile, charge);
+
+            if !finished {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// adds `charge` to the numbers around `tile`. returns whether they can
+    /// all still be met.
+    fn assign(&mut self, tile: usize, charge: i32) -> bool {
+        let (least, most) = (*self.frontier.charges.start(), *self.frontier.charges.end());
+        let mut fits = true;
+
+        for &index in &self.frontier.links[tile] {
+            let constraint = &self.frontier.constraints[index];
+            let (sum, left, mines) = &mut self.sums[index];
+            *sum += charge;
+            *left -= 1;
+            *mines += (charge != 0) as usize;
+
+            let reachable = (*sum + *left as i32 * least)..=(*sum + *left as i32 * most);
+            let met = *left > 0 || *mines > 0 || !constraint.needs_mine;
+            fits &= reachable.contains(&constraint.sum) && met;
+        }
+
+        fits
+    }
+
+    fn unassign(&mut self, tile: usize, charge: i32) {
+        for &index in &self.frontier.links[tile] {
+            let (sum, left, mines) = &mut self.sums[index];
+            *sum -= charge;
+            *left += 1;
+            *mines -= (charge != 0) as usize;
+        }
+    }
+
+    fn tally(&mut self) {
+        self.layouts += 1;
+        for (position, &charge) in self.charges.iter().enumerate() {
+            self.mines[position] += (charge != 0) as u64;
+            self.mine_sums[position] += charge.max(0) as f64;
+            self.same[position] = match self.same[position] {
+                None => Some(Some(charge)),
+                Some(Some(same)) if same == charge => Some(Some(same)),
+                Some(_) => Some(None),
+            };
+        }
+    }
+
+    fn odds(&self, position: usize) -> Odds {
+        if let Some(Some(charge)) = self.same[position] {
+            return Odds::Known(charge);
+        }
+
+        let layouts = self.layouts as f64;
+        Odds::Likely {
+            mine: self.mines[position] as f64 / layouts,
+            mines: self.mine_sums[position] / layouts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::resources::{BoardRules, FirstClick};
+
+    /// board with a mine on each of `mines` and its `revealed` rows flipped.
+    fn board(width: i64, height: i64, mines: &[(i64, i64)], revealed: &[i64]) -> TileMap {
+        let mut tilemap = TileMap::new(width, height, 1, mines.len() as u64, BoardRules::default());
+        for &(col, row) in mines {
+            tilemap[Coordinates::new(col, row)].add_mine(1);
+        }
+        tilemap.update_tile_numbers();
+
+        for &row in revealed {
+            for col in 0..width {
+                tilemap[Coordinates::new(col, row)].flip();
+            }
+        }
+        tilemap
+    }
+
+    #[test]
+    fn starts_in_the_middle() {
+        let tilemap = board(9, 7, &[(0, 0)], &[]);
+        assert_eq!(solve(&tilemap), [BotMove::Reveal(Coordinates::new(4, 3))]);
+    }
+
+    #[test]
+    fn finds_forced_moves() {
+        // 1 2 1 over three hidden tiles: mine, safe, mine
+        let tilemap = board(3, 2, &[(0, 1), (2, 1)], &[0]);
+        let mut moves = solve(&tilemap);
+        moves.sort_by_key(|bot_move| bot_move.coordinates().col);
+
+        assert_eq!(
+            moves,
+            [
+                BotMove::Mark(Coordinates::new(0, 1), 1),
+                BotMove::Reveal(Coordinates::new(1, 1)),
+                BotMove::Mark(Coordinates::new(2, 1), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn trusts_marks_on_the_board() {
+        let mut tilemap = board(3, 2, &[(0, 1), (2, 1)], &[0]);
+        tilemap[Coordinates::new(0, 1)].toggle_mark();
+        tilemap[Coordinates::new(2, 1)].toggle_mark();
+        assert_eq!(solve(&tilemap), [BotMove::Reveal(Coordinates::new(1, 1))]);
+    }
+
+    #[test]
+    fn guesses_when_the_search_runs_out() {
+        // every column holds a single mine, above or below the revealed row,
+        // which leaves 2^30 layouts and nothing certain
+        let mines: Vec<(i64, i64)> = (0..30).map(|col| (col, (col % 2) * 2)).collect();
+        let tilemap = board(30, 3, &mines, &[1]);
+
+        let moves = solve(&tilemap);
+        assert!(matches!(moves[..], [BotMove::Guess(_)]));
+    }
+
+    #[test]
+    fn plays_only_safe_moves_until_it_guesses() {
+        for seed in 0..30 {
+            let rules = BoardRules {
+                first_click: FirstClick::Opening,
+                ..Default::default()
+            };
+            let mut tilemap = TileMap::new(9, 9, 1, 10, rules);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let start = Coordinates::new(4, 4);
+            tilemap.generate_mines_with(&start, &mut rng).unwrap();
+            tilemap.reveal(start);
+
+            for _ in 0..100 {
+                let moves = solve(&tilemap);
+                if let [BotMove::Guess(coordinates)] = moves[..] {
+                    if tilemap[coordinates].contains_mine() {
+                        break;
+                    }
+                    tilemap.reveal(coordinates);
+                    continue;
+                }
+                if moves.is_empty() {
+                    break;
+                }
+
+                for bot_move in moves {
+                    match bot_move {
+                        BotMove::Reveal(coordinates) => {
+                            assert!(!tilemap[coordinates].contains_mine(), "seed {seed}");
+                            tilemap.reveal(coordinates);
+                        }
+                        BotMove::Mark(coordinates, charge) => {
+                            assert_eq!(tilemap[coordinates].charge(), charge, "seed {seed}");
+                            tilemap[coordinates].toggle_mark();
+                        }
+                        BotMove::Guess(_) => panic!("guessed next to certain moves"),
+                    }
+                }
+            }
+        }
+    }
+}
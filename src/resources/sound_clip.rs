@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use bevy::audio::{Decodable, Source};
+use bevy::prelude::*;
+
+pub const SAMPLE_RATE: u32 = 44_100;
+
+/// short mono sound synthesized at startup, so the game ships no audio files.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct SoundClip {
+    samples: Arc<[f32]>,
+}
+
+impl SoundClip {
+    /// samples `duration` seconds of `wave`, called with the time in seconds.
+    pub fn synthesize(duration: f32, mut wave: impl FnMut(f32) -> f32) -> Self {
+        let count = (duration * SAMPLE_RATE as f32) as usize;
+        let samples = (0..count)
+            .map(|index| wave(index as f32 / SAMPLE_RATE as f32).clamp(-1.0, 1.0))
+            .collect();
+
+        Self { samples }
+    }
+}
+
+pub struct SoundClipDecoder {
+    samples: Arc<[f32]>,
+    position: usize,
+}
+
+impl Iterator for SoundClipDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.samples.get(self.position).copied();
+        self.position += 1;
+        sample
+    }
+}
+
+impl Source for SoundClipDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(
+            self.samples.len() as f32 / SAMPLE_RATE as f32,
+        ))
+    }
+}
+
+impl Decodable for SoundClip {
+    type DecoderItem = f32;
+    type Decoder = SoundClipDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        SoundClipDecoder {
+            samples: self.samples.clone(),
+            position: 0,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use super::SoundClip;
+
+/// exponential fade, `rate` being how fast the sound dies down.
+fn decay(t: f32, rate: f32) -> f32 {
+    (-t * rate).exp()
+}
+
+/// short sine blip starting at `t = 0`, silent before.
+fn blip(t: f32, frequency: f32, rate: f32) -> f32 {
+    if t < 0.0 {
+        return 0.0;
+    }
+
+    (TAU * frequency * t).sin() * decay(t, rate)
+}
+
+/// sine sweeping linearly from `from` to `to` hertz over `duration` seconds.
+fn chirp(t: f32, from: f32, to: f32, duration: f32) -> f32 {
+    let sweep = (to - from) / duration;
+    (TAU * (from * t + sweep * t * t / 2.0)).sin()
+}
+
+#[derive(Debug, Resource)]
+pub struct SoundHandles {
+    pub reveal: Handle<SoundClip>,
+    pub flag_placed: Handle<SoundClip>,
+    pub flag_removed: Handle<SoundClip>,
+    pub chord: Handle<SoundClip>,
+    pub explosion: Handle<SoundClip>,
+    pub win: Handle<SoundClip>,
+    pub click: Handle<SoundClip>,
+    pub music: Handle<SoundClip>,
+}
+
+impl SoundHandles {
+    pub fn new(clips: &mut Assets<SoundClip>) -> Self {
+        let reveal = SoundClip::synthesize(0.08, |t| blip(t, 880.0, 40.0) * 0.4);
+
+        let flag_placed = SoundClip::synthesize(0.09, |t| {
+            chirp(t, 500.0, 900.0, 0.09) * decay(t, 25.0) * 0.4
+        });
+        let flag_removed = SoundClip::synthesize(0.09, |t| {
+            chirp(t, 900.0, 500.0, 0.09) * decay(t, 25.0) * 0.4
+        });
+
+        let chord = SoundClip::synthesize(0.14, |t| {
+            (blip(t, 1000.0, 50.0) + blip(t - 0.06, 1200.0, 50.0)) * 0.35
+        });
+
+        // low passed noise over a low thump
+        let mut rumble = 0.0;
+        let explosion = SoundClip::synthesize(1.2, |t| {
+            let noise = rand::random::<f32>() * 2.0 - 1.0;
+            rumble += (noise - rumble) * 0.08;
+            let thump = (TAU * 55.0 * t).sin() * decay(t, 4.0);
+            (rumble * 2.5 + thump * 0.6) * decay(t, 3.5)
+        });
+
+        // rising C major arpeggio
+        let notes = [523.25, 659.25, 783.99, 1046.5];
+        let win = SoundClip::synthesize(0.9, |t| {
+            notes
+                .iter()
+                .enumerate()
+                .map(|(index, &frequency)| blip(t - index as f32 * 0.12, frequency, 6.0))
+                .sum::<f32>()
+                * 0.25
+        });
+
+        let click = SoundClip::synthesize(0.03, |t| blip(t, 1500.0, 120.0) * 0.3);
+
+        // two soft chords fading into each other. every frequency and the
+        // tremolo complete whole cycles in the 8 seconds, so the loop is seamless
+        let a_minor = [220.0, 261.625, 329.625];
+        let f_major = [174.625, 220.0, 261.625];
+        let music = SoundClip::synthesize(8.0, |t| {
+            let chord = |frequencies: &[f32; 3]| -> f32 {
+                frequencies
+                    .iter()
+                    .map(|frequency| (TAU * frequency * t).sin())
+                    .sum()
+            };
+            let blend = 0.5 + 0.5 * (TAU * t / 8.0).cos();
+            let tremolo = 0.8 + 0.2 * (TAU * t / 2.0).cos();
+            (chord(&a_minor) * blend + chord(&f_major) * (1.0 - blend)) * tremolo * 0.08
+        });
+
+        Self {
+            reveal: clips.add(reveal),
+            flag_placed: clips.add(flag_placed),
+            flag_removed: clips.add(flag_removed),
+            chord: clips.add(chord),
+            explosion: clips.add(explosion),
+            win: clips.add(win),
+            click: clips.add(click),
+            music: clips.add(music),
+        }
+    }
+}
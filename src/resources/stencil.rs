@@ -0,0 +1,119 @@
+use super::Topology;
+
+const RING_OFFSETS: [(i64, i64); 16] = [
+    (-2, -2),
+    (-1, -2),
+    (0, -2),
+    (1, -2),
+    (2, -2),
+    (-2, -1),
+    (2, -1),
+    (-2, 0),
+    (2, 0),
+    (-2, 1),
+    (2, 1),
+    (-2, 2),
+    (-1, 2),
+    (0, 2),
+    (1, 2),
+    (2, 2),
+];
+const KNIGHT_OFFSETS: [(i64, i64); 8] = [
+    (-1, -2),
+    (1, -2),
+    (-2, -1),
+    (2, -1),
+    (-2, 1),
+    (2, 1),
+    (-1, 2),
+    (1, 2),
+];
+const ORTHOGONAL_OFFSETS: [(i64, i64); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// tiles a number counts the mines of, and that flood fill and chording
+/// spread to, as offsets from the numbered tile.
+///
+/// stencils are laid out on the square grid. hex boards always use their
+/// six touching tiles.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Stencil {
+    /// every touching tile.
+    #[default]
+    Standard,
+    /// the four tiles sharing a side.
+    Orthogonal,
+    /// the eight tiles a chess knight reaches.
+    Knight,
+    /// the outer ring of the 5x5 square around the tile.
+    Ring,
+    /// `(col, row)` offsets given in the settings.
+    Custom(Vec<(i64, i64)>),
+}
+
+impl Stencil {
+    pub const PRESETS: [Stencil; 4] = [Self::Standard, Self::Orthogonal, Self::Knight, Self::Ring];
+
+    /// the following preset. custom stencils are only set from the settings file.
+    pub fn next(&self) -> Self {
+        let index = Self::PRESETS.iter().position(|stencil| stencil == self);
+        match index {
+            Some(index) => Self::PRESETS[(index + 1) % Self::PRESETS.len()].clone(),
+            None => Self::Standard,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Orthogonal => "orthogonal",
+            Self::Knight => "knight",
+            Self::Ring => "ring",
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    /// a preset name, or a custom stencil as space separated `col,row` offsets.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        if let Some(stencil) = Self::PRESETS
+            .into_iter()
+            .find(|stencil| stencil.name() == value)
+        {
+            return Ok(stencil);
+        }
+
+        let mut offsets = Vec::new();
+        for pair in value.split_whitespace() {
+            let offset = pair
+                .split_once(',')
+                .and_then(|(col, row)| Some((col.trim().parse().ok()?, row.trim().parse().ok()?)))
+                .ok_or_else(|| {
+                    format!("expected a stencil name or `col,row` offsets, found `{pair}`")
+                })?;
+
+            if offset != (0, 0) && !offsets.contains(&offset) {
+                offsets.push(offset);
+            }
+        }
+
+        if offsets.is_empty() {
+            return Err(format!("stencil `{value}` has no offsets"));
+        }
+
+        Ok(Self::Custom(offsets))
+    }
+
+    /// `(col, row)` offsets of the neighbors of a tile in `row`.
+    pub fn offsets(&self, topology: Topology, row: i64) -> &[(i64, i64)] {
+        if topology != Topology::Square {
+            return topology.neighbor_offsets(row);
+        }
+
+        match self {
+            Self::Standard => topology.neighbor_offsets(row),
+            Self::Orthogonal => &ORTHOGONAL_OFFSETS,
+            Self::Knight => &KNIGHT_OFFSETS,
+            Self::Ring => &RING_OFFSETS,
+            Self::Custom(offsets) => offsets,
+        }
+    }
+}
@@ -1,48 +1,82 @@
-use bevy::prelude::*;
-
 use super::Shade;
 
 #[derive(Debug)]
 pub struct Tile {
-    id: Entity,
     shade: Shade,
-    mine: bool,
+    mines: u8,
+    /// an anti-mine takes one off the numbers around it. tiles holding one
+    /// hold no mines, but revealing them loses the game all the same.
+    anti_mine: bool,
     flipped: bool,
-    number: Option<usize>,
-    mark: bool,
+    /// sum of the mines around the tile, less the anti-mines. `None` when
+    /// there are neither, unlike `Some(0)` which hides a cancellation.
+    number: Option<i32>,
+    /// flags placed on the tile. only boards with several mines per tile
+    /// stack more than one.
+    flags: u8,
+    anti_flag: bool,
+    /// masked out of the board's shape. void tiles hold no mines and can not be flipped.
+    void: bool,
 }
 
 impl Tile {
-    pub fn new(commands: &mut Commands, shade: Shade) -> Self {
+    pub fn new(shade: Shade) -> Self {
         Self {
-            id: commands.spawn_empty().id(),
-            mine: false,
+            mines: 0,
+            anti_mine: false,
             flipped: false,
-            mark: false,
+            flags: 0,
+            anti_flag: false,
             number: None,
+            void: false,
             shade,
         }
     }
 
+    /// a hole in the board, see `BoardShape`.
+    pub fn void(shade: Shade) -> Self {
+        Self {
+            void: true,
+            ..Self::new(shade)
+        }
+    }
+
+    pub fn is_void(&self) -> bool {
+        self.void
+    }
+
     pub fn is_hoverable(&self) -> bool {
-        !self.flipped && !self.mark
+        !self.flipped && !self.is_marked()
     }
 
-    /// adds mine to the tile.
+    /// adds a mine to the tile, unless it already holds `limit` of them.
     ///
-    /// returns `true` if the tile did not previously have a mine
+    /// returns `true` if a mine was added
     /// `false` otherwise.
-    pub fn set_mine(&mut self) -> bool {
-        if self.mine {
+    pub fn add_mine(&mut self, limit: u8) -> bool {
+        if self.mines >= limit || self.anti_mine || self.void {
             return false;
         }
 
-        self.mine = true;
+        self.mines += 1;
+        true
+    }
+
+    /// turns a tile holding no mine into an anti-mine.
+    ///
+    /// returns `true` if the tile did not previously hold a mine of either kind
+    /// `false` otherwise.
+    pub fn set_anti_mine(&mut self) -> bool {
+        if self.contains_mine() || self.void {
+            return false;
+        }
+
+        self.anti_mine = true;
         true
     }
 
     pub fn flip(&mut self) -> bool {
-        if self.mark || self.flipped {
+        if self.is_marked() || self.flipped || self.void {
             return false;
         }
 
@@ -54,40 +88,106 @@ impl Tile {
         self.flipped
     }
 
+    /// whether the tile holds a mine of either kind, which loses the game
+    /// when revealed.
     pub fn contains_mine(&self) -> bool {
-        self.mine
+        self.mines > 0 || self.anti_mine
+    }
+
+    pub fn contains_anti_mine(&self) -> bool {
+        self.anti_mine
+    }
+
+    /// what the tile adds to the numbers around it.
+    pub fn charge(&self) -> i32 {
+        self.mines as i32 - self.anti_mine as i32
     }
 
-    pub fn id(&self) -> Entity {
-        self.id
+    pub fn mines(&self) -> u8 {
+        self.mines
     }
 
-    pub fn number(&self) -> Option<usize> {
+    pub fn number(&self) -> Option<i32> {
         self.number
     }
 
-    pub fn set_number(&mut self, number: usize) {
-        if number == 0 {
-            return;
+    /// sets the number of a tile touching mines of either kind. tiles touching
+    /// none keep no number, even when `number` is zero.
+    pub fn set_number(&mut self, number: i32, touches_mines: bool) {
+        self.number = touches_mines.then_some(number);
+    }
+
+    pub fn toggle_mark(&mut self) -> bool {
+        self.add_flag(1, false)
+    }
+
+    /// stacks another flag on the tile. past `limit` flags, the stack turns
+    /// into an anti-flag when `anti_flags` is set, and is cleared otherwise.
+    pub fn add_flag(&mut self, limit: u8, anti_flags: bool) -> bool {
+        if self.flipped {
+            return false;
         }
 
-        self.number = Some(number);
+        if self.anti_flag {
+            self.anti_flag = false;
+        } else if self.flags < limit {
+            self.flags += 1;
+        } else {
+            self.flags = 0;
+            self.anti_flag = anti_flags;
+        }
+        true
     }
 
-    pub fn toggle_mark(&mut self) -> bool {
+    /// takes one flag or the anti-flag off the tile. returns `false` if there was none.
+    pub fn remove_flag(&mut self) -> bool {
+        if self.flipped || !self.is_marked() {
+            return false;
+        }
+
+        match self.anti_flag {
+            true => self.anti_flag = false,
+            false => self.flags -= 1,
+        }
+        true
+    }
+
+    pub fn set_flags(&mut self, flags: u8) -> bool {
+        if self.flipped {
+            return false;
+        }
+
+        self.flags = flags;
+        true
+    }
+
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    pub fn set_anti_flag(&mut self, anti_flag: bool) -> bool {
         if self.flipped {
             return false;
         }
 
-        self.mark = !self.mark;
+        self.anti_flag = anti_flag;
         true
     }
 
+    pub fn has_anti_flag(&self) -> bool {
+        self.anti_flag
+    }
+
+    /// what the marks on the tile claim it adds to the numbers around it.
+    pub fn marked_charge(&self) -> i32 {
+        self.flags as i32 - self.anti_flag as i32
+    }
+
     pub fn shade(&self) -> Shade {
         self.shade
     }
 
     pub fn is_marked(&self) -> bool {
-        self.mark
+        self.flags > 0 || self.anti_flag
     }
 }
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use super::Coordinates;
+
+/// length of the flip played when a tile is revealed, in seconds.
+const FLIP_SECS: f32 = 0.18;
+/// length of the pop played when a tile is flagged, in seconds.
+const POP_SECS: f32 = 0.2;
+/// how much bigger a tile gets at the peak of a pop.
+const POP_SCALE: f32 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileAnimationKind {
+    /// the tile turns over, showing its hidden side until halfway through.
+    Flip,
+    /// the tile briefly grows and settles back.
+    Pop,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TileAnimation {
+    pub kind: TileAnimationKind,
+    /// `Time::elapsed_secs` at which the animation starts, possibly in the future.
+    pub start: f32,
+}
+
+impl TileAnimation {
+    fn duration(&self) -> f32 {
+        match self.kind {
+            TileAnimationKind::Flip => FLIP_SECS,
+            TileAnimationKind::Pop => POP_SECS,
+        }
+    }
+
+    pub fn is_finished(&self, now: f32) -> bool {
+        now >= self.start + self.duration()
+    }
+}
+
+/// how an animated tile is drawn at a given time.
+#[derive(Debug, Clone, Copy)]
+pub struct TilePose {
+    /// draw the tile as if it was still unrevealed.
+    pub hidden: bool,
+    /// scale of the tile around its center.
+    pub scale: Vec2,
+}
+
+/// tiles currently animating. tile state changes at once, only the way the
+/// chunk meshes draw it lags behind.
+#[derive(Debug, Resource, Default)]
+pub struct TileAnimations {
+    pub tiles: HashMap<Coordinates, TileAnimation>,
+}
+
+impl TileAnimations {
+    pub fn start(&mut self, coordinates: Coordinates, kind: TileAnimationKind, start: f32) {
+        self.tiles
+            .insert(coordinates, TileAnimation { kind, start });
+    }
+
+    /// pose of the tile at `now`, if it is animating.
+    pub fn pose(&self, coordinates: &Coordinates, now: f32) -> Option<TilePose> {
+        let animation = self.tiles.get(coordinates)?;
+        if animation.is_finished(now) {
+            return None;
+        }
+
+        let progress = ((now - animation.start) / animation.duration()).max(0.0);
+
+        let pose = match animation.kind {
+            TileAnimationKind::Flip => TilePose {
+                hidden: progress < 0.5,
+                scale: Vec2::new((PI * progress).cos().abs(), 1.0),
+            },
+            TileAnimationKind::Pop => TilePose {
+                hidden: false,
+                scale: Vec2::splat(1.0 + POP_SCALE * (PI * progress).sin()),
+            },
+        };
+
+        Some(pose)
+    }
+}
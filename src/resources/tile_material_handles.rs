@@ -1,65 +1,38 @@
 use bevy::prelude::*;
 
-use super::{Shade, Tile};
+use super::Palette;
 
+/// materials of the entities drawn over the board. tiles themselves are
+/// colored per vertex in their chunk mesh.
 #[derive(Debug, Resource)]
 pub struct TileMaterialHandles {
-    pub unflipped_light: Handle<ColorMaterial>,
-    pub unflipped_dark: Handle<ColorMaterial>,
-    pub flipped_light: Handle<ColorMaterial>,
-    pub flipped_dark: Handle<ColorMaterial>,
     pub hover: Handle<ColorMaterial>,
-    pub mine: Handle<ColorMaterial>,
+    pub cursor: Handle<ColorMaterial>,
     pub mark: Handle<ColorMaterial>,
+    /// dots showing which tiles a number counts.
+    pub stencil: Handle<ColorMaterial>,
 }
 
 impl TileMaterialHandles {
-    pub fn new(mut materials: ResMut<Assets<ColorMaterial>>) -> Self {
-        let unflipped_light = materials.add(Color::srgb_u8(10, 10, 10));
-        let unflipped_dark = materials.add(Color::srgb_u8(0, 0, 0));
-        let flipped_light = materials.add(Color::srgb_u8(50, 50, 50));
-        let flipped_dark = materials.add(Color::srgb_u8(40, 40, 40));
+    pub fn new(mut materials: ResMut<Assets<ColorMaterial>>, palette: Palette) -> Self {
         let hover = materials.add(Color::srgb(7.5, 7.5, 7.5));
-        let mine = materials.add(Color::srgb_u8(245, 42, 32));
-        let mark = materials.add(Color::srgb(0.0, 4.5, 0.0));
+        let cursor = materials.add(Color::srgba(7.5, 7.5, 7.5, 0.15));
+        let mark = materials.add(palette.mark_color());
+        let stencil = materials.add(Color::srgba(7.5, 7.5, 7.5, 0.35));
 
         Self {
-            unflipped_light,
-            unflipped_dark,
-            flipped_light,
-            flipped_dark,
             hover,
-            mine,
+            cursor,
             mark,
+            stencil,
         }
     }
 
-    pub fn get_material(&self, tile: &Tile) -> Handle<ColorMaterial> {
-        if tile.contains_mine() && tile.is_flipped() {
-            return self.mine.clone();
-        }
-
-        if tile.is_marked() {
-            return self.mark.clone();
-        }
-
-        match tile.is_flipped() {
-            true => self.get_flipped_tile_material(tile),
-            false => self.get_unflipped_tile_material(tile),
-        }
-    }
-
-    fn get_unflipped_tile_material(&self, tile: &Tile) -> Handle<ColorMaterial> {
-        match tile.shade() {
-            Shade::Light => self.unflipped_light.clone(),
-            Shade::Dark => self.unflipped_dark.clone(),
-        }
-    }
-
-    fn get_flipped_tile_material(&self, tile: &Tile) -> Handle<ColorMaterial> {
-        match tile.shade() {
-            Shade::Light => self.flipped_light.clone(),
-            Shade::Dark => self.flipped_dark.clone(),
+    /// recolors the mark material in place, so every entity using it
+    /// picks up the new palette without swapping handles.
+    pub fn apply_palette(&self, palette: Palette, materials: &mut Assets<ColorMaterial>) {
+        if let Some(mark) = materials.get_mut(&self.mark) {
+            mark.color = palette.mark_color();
         }
     }
 }
@@ -1,136 +1,761 @@
+use std::collections::{HashMap, HashSet};
+
 use bevy::prelude::*;
-use rand::{thread_rng, Rng};
+use rand::{seq::SliceRandom, thread_rng, Rng};
 use std::ops::{Index, IndexMut};
 
 use crate::resources::Coordinates;
-use crate::MINE_COUNT;
+use crate::{CHUNK_SIZE, GHOST_TILES, TILE_SIZE};
 
-use super::{Shade, Tile};
+use super::endless_generator::chunk_index;
+use super::{BoardRules, EndlessGenerator, Shade, Tile};
 
 #[derive(Debug, Resource)]
 pub struct TileMap {
+    /// size of a bounded board, zero in endless mode.
     pub width: i64,
     pub height: i64,
-    pub tiles: Vec<Vec<Tile>>,
+    /// layers of a bounded board, stacked along a third axis. endless boards
+    /// have a single one.
+    pub depth: i64,
+    pub mine_count: u64,
+    pub rules: BoardRules,
+    /// layer shown, and played on, out of `depth`.
+    pub view_layer: i64,
+    storage: TileStorage,
+}
+
+#[derive(Debug)]
+enum TileStorage {
+    /// `depth` layers of `height` rows of `width` tiles, one after the other.
+    Bounded(Vec<Tile>),
+    /// chunks of `CHUNK_SIZE` x `CHUNK_SIZE` tiles, generated as they are needed.
+    Endless {
+        generator: EndlessGenerator,
+        chunks: HashMap<IVec2, Vec<Tile>>,
+    },
 }
 
 impl Index<Coordinates> for TileMap {
     type Output = Tile;
 
     fn index(&self, coordinates: Coordinates) -> &Self::Output {
-        let col = coordinates.col as usize;
-        let row = coordinates.row as usize;
-        &self.tiles[row][col]
+        match &self.storage {
+            TileStorage::Bounded(tiles) => {
+                let index = tile_index(self.width, self.height, &coordinates);
+                &tiles[index]
+            }
+            TileStorage::Endless { chunks, .. } => {
+                let chunk = chunks
+                    .get(&coordinates.chunk())
+                    .expect("tile of a chunk that was not generated yet");
+                &chunk[chunk_index(&coordinates)]
+            }
+        }
     }
 }
 
 impl IndexMut<Coordinates> for TileMap {
+    /// in endless mode, generates the chunk holding the tile if needed.
     fn index_mut(&mut self, coordinates: Coordinates) -> &mut Self::Output {
-        let col = coordinates.col as usize;
-        let row = coordinates.row as usize;
-
-        &mut self.tiles[row][col]
+        match &mut self.storage {
+            TileStorage::Bounded(tiles) => {
+                let index = tile_index(self.width, self.height, &coordinates);
+                &mut tiles[index]
+            }
+            TileStorage::Endless { generator, chunks } => {
+                let chunk = chunks
+                    .entry(coordinates.chunk())
+                    .or_insert_with_key(|&chunk| generator.generate_chunk(chunk));
+                &mut chunk[chunk_index(&coordinates)]
+            }
+        }
     }
 }
 
 impl TileMap {
-    pub fn new(commands: &mut Commands, width: i64, height: i64) -> Self {
-        let mut tiles = Vec::new();
-        for row in 0..height {
-            let mut tile_row = Vec::new();
-            for col in 0..width {
-                let shade = Shade::from_coordinates(row, col);
-                let tile = Tile::new(commands, shade);
-                tile_row.push(tile);
+    pub fn new(width: i64, height: i64, depth: i64, mine_count: u64, rules: BoardRules) -> Self {
+        let mut tiles = Vec::with_capacity((width * height * depth) as usize);
+        for layer in 0..depth {
+            for row in 0..height {
+                for col in 0..width {
+                    let coordinates = Coordinates::new(col, row).with_layer(layer);
+                    let shade = Shade::from_coordinates(&coordinates, rules.topology);
+                    let tile = match rules.shape.contains(col, row, width, height) {
+                        true => Tile::new(shade),
+                        false => Tile::void(shade),
+                    };
+                    tiles.push(tile);
+                }
             }
-
-            tiles.push(tile_row);
         }
 
         Self {
             width,
             height,
-            tiles,
+            depth,
+            mine_count,
+            rules,
+            view_layer: 0,
+            storage: TileStorage::Bounded(tiles),
         }
     }
 
-    pub fn generate_mines(&mut self, mouse_coordinates: &Coordinates) {
-        let mine_count = MINE_COUNT;
-
-        let mut mines = 0;
+    /// board without edges, with a `density` fraction of its tiles holding mines.
+    pub fn new_endless(seed: u64, density: f64, rules: BoardRules) -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            depth: 1,
+            mine_count: 0,
+            rules: rules.clone(),
+            view_layer: 0,
+            storage: TileStorage::Endless {
+                generator: EndlessGenerator::new(seed, density, rules),
+                chunks: HashMap::new(),
+            },
+        }
+    }
 
-        while mines < mine_count {
-            let mine_coords = Coordinates::new(
-                thread_rng().gen_range(0..self.width),
-                thread_rng().gen_range(0..self.height),
-            );
+    pub fn is_endless(&self) -> bool {
+        matches!(self.storage, TileStorage::Endless { .. })
+    }
 
-            if mine_coords.manhattan_distance(mouse_coordinates) <= 3 {
-                continue;
+    /// whether the chunk overlaps the board. always true in endless mode.
+    pub fn chunk_in_bounds(&self, chunk: IVec2) -> bool {
+        match self.storage {
+            TileStorage::Bounded(_) => {
+                let last = Coordinates::new(self.width - 1, self.height - 1).chunk();
+                chunk.cmpge(IVec2::ZERO).all() && chunk.cmple(last).all()
             }
+            TileStorage::Endless { .. } => true,
+        }
+    }
 
-            let tile = &mut self[mine_coords];
+    /// makes sure the tiles of a chunk exist. bounded boards always hold all of them.
+    pub fn generate_chunk(&mut self, chunk: IVec2) {
+        if let TileStorage::Endless { generator, chunks } = &mut self.storage {
+            chunks
+                .entry(chunk)
+                .or_insert_with_key(|&chunk| generator.generate_chunk(chunk));
+        }
+    }
 
-            if tile.set_mine() {
-                mines += 1;
-            }
+    /// generates the chunks of every neighbor of `coordinates`, so a revealed
+    /// tile at a chunk edge can be read together with its neighbors.
+    pub fn generate_around(&mut self, coordinates: &Coordinates) {
+        if !self.is_endless() {
+            return;
         }
 
-        self.update_tile_numbers();
+        let chunks: Vec<IVec2> = self.neighbors(coordinates).map(|n| n.chunk()).collect();
+        for chunk in chunks {
+            self.generate_chunk(chunk);
+        }
+    }
+
+    /// places the mines around the first tile revealed, at `mouse_coordinates`,
+    /// keeping clear the tiles the first click policy asks for. with one mine
+    /// per tile, every layout of the mines over the remaining tiles is equally
+    /// likely.
+    ///
+    /// tiles holding several mines offer a slot per mine, and every slot is
+    /// equally likely to be filled. layouts are then not equally likely: one
+    /// with two single mines can be picked from four pairs of slots on tiles
+    /// holding two, where a stack of two fills a single pair. mines lean
+    /// towards spreading out over stacking up.
+    ///
+    /// returns an error, placing nothing, when the mines do not fit in the
+    /// remaining tiles.
+    pub fn generate_mines(&mut self, mouse_coordinates: &Coordinates) -> Result<(), String> {
+        self.generate_mines_with(mouse_coordinates, &mut thread_rng())
     }
 
-    fn update_tile_numbers(&mut self) {
-        for row in 0..self.height {
-            for col in 0..self.width {
-                let coordinates = Coordinates::new(col, row);
-                let mine_count = self
-                    .get_neighbors(&coordinates)
-                    .into_iter()
-                    .filter(|&neighbor| self[neighbor].contains_mine())
-                    .count();
+    /// `generate_mines` drawing from `rng`, so a seeded one always gives the
+    /// same layout.
+    pub fn generate_mines_with(
+        &mut self,
+        mouse_coordinates: &Coordinates,
+        rng: &mut impl Rng,
+    ) -> Result<(), String> {
+        if let TileStorage::Endless { generator, chunks } = &mut self.storage {
+            generator.start = Some(*mouse_coordinates);
 
-                self[coordinates].set_number(mine_count);
+            // chunks generated before the first click hold no mines yet
+            for (&chunk, tiles) in chunks.iter_mut() {
+                let generated = generator.generate_chunk(chunk);
+                for (tile, mut generated) in tiles.iter_mut().zip(generated) {
+                    generated.set_flags(tile.flags());
+                    *tile = generated;
+                }
             }
+            return Ok(());
+        }
+
+        let mine_count = self.mine_count as usize;
+        let anti_mine_count = self.rules.anti_mines as usize;
+        let mines_per_tile = self.rules.mines_per_tile as usize;
+        let first_click = self.rules.first_click;
+        let start_neighbors = self.get_neighbors(mouse_coordinates);
+        let topology = self.rules.topology;
+        let wrap = (self.rules.wrap && !self.is_endless()).then_some((self.width, self.height));
+
+        let mut eligible: Vec<Coordinates> = self
+            .iter()
+            .map(|(coordinates, _)| coordinates)
+            .filter(|coordinates| {
+                !first_click.keeps_clear(
+                    coordinates,
+                    mouse_coordinates,
+                    &start_neighbors,
+                    topology,
+                    wrap,
+                )
+            })
+            .collect();
+
+        // anti-mines take a whole tile, the tiles left take several mines each
+        let fits = anti_mine_count <= eligible.len()
+            && mine_count <= (eligible.len() - anti_mine_count) * mines_per_tile;
+        if !fits {
+            let mines = match anti_mine_count {
+                0 => format!("{mine_count} mines"),
+                _ => format!("{mine_count} mines and {anti_mine_count} anti-mines"),
+            };
+            return Err(format!(
+                "{mines} do not fit in the {} tiles away from the first click",
+                eligible.len()
+            ));
+        }
+
+        let (anti_mines, rest) = eligible.partial_shuffle(rng, anti_mine_count);
+        for &coordinates in anti_mines.iter() {
+            self[coordinates].set_anti_mine();
+        }
+
+        // every mine a tile can hold gets its own slot, see above for the bias
+        let mut slots: Vec<Coordinates> = rest
+            .iter()
+            .flat_map(|&coordinates| std::iter::repeat_n(coordinates, mines_per_tile))
+            .collect();
+        let (mines, _) = slots.partial_shuffle(rng, mine_count);
+        for &coordinates in mines.iter() {
+            self[coordinates].add_mine(mines_per_tile as u8);
         }
+
+        self.update_tile_numbers();
+        Ok(())
     }
 
-    pub fn get_neighbors(&self, coordinates: &Coordinates) -> Vec<Coordinates> {
+    /// flips the tile at `coordinates` the way a click does: when it is empty,
+    /// the tiles around it flip along, and so on. returns the tiles flipped in
+    /// the order they flipped, a tile holding a mine being the only one.
+    pub fn reveal(&mut self, coordinates: Coordinates) -> Vec<Coordinates> {
+        let mut flipped = Vec::new();
+        if !self[coordinates].flip() {
+            return flipped;
+        }
+        flipped.push(coordinates);
+
+        // tiles flip as soon as they are reached, so none is visited twice.
+        // `flipped` doubles as the queue of tiles to spread from.
         let mut neighbors = Vec::new();
+        let mut next = 0;
+        while let Some(&coordinates) = flipped.get(next) {
+            next += 1;
+            if self[coordinates].contains_mine() {
+                break;
+            }
 
-        for row_offset in -1..=1 {
-            for col_offset in -1..=1 {
-                if row_offset == 0 && col_offset == 0 {
-                    continue;
-                }
+            // neighbors across a chunk edge may not exist yet on an endless board
+            self.generate_around(&coordinates);
 
-                let neighbor_row = coordinates.row + row_offset;
-                let neighbor_col = coordinates.col + col_offset;
+            if self[coordinates].number().is_some() {
+                continue;
+            }
 
-                if neighbor_col < 0 || neighbor_col >= self.width {
-                    continue;
+            neighbors.extend(self.neighbors(&coordinates));
+            for neighbor in neighbors.drain(..) {
+                if self[neighbor].flip() {
+                    flipped.push(neighbor);
                 }
+            }
+        }
 
-                if neighbor_row < 0 || neighbor_row >= self.height {
-                    continue;
+        flipped
+    }
+
+    /// counts the mines of either kind around every tile. `generate_mines`
+    /// does so once the mines are in place.
+    pub fn update_tile_numbers(&mut self) {
+        for layer in 0..self.depth {
+            for row in 0..self.height {
+                for col in 0..self.width {
+                    let coordinates = Coordinates::new(col, row).with_layer(layer);
+                    let (number, touches_mines) = self.neighbors(&coordinates).fold(
+                        (0, false),
+                        |(number, touches_mines), neighbor| {
+                            let neighbor = &self[neighbor];
+                            (
+                                number + neighbor.charge(),
+                                touches_mines || neighbor.contains_mine(),
+                            )
+                        },
+                    );
+
+                    self[coordinates].set_number(number, touches_mines);
                 }
+            }
+        }
+    }
+
+    /// tiles the stencil reaches from `coordinates`. on a layered board, the
+    /// stencil also reaches into the layers above and below, along with the
+    /// tile straight across, so a standard stencil reaches 26 tiles.
+    pub fn get_neighbors(&self, coordinates: &Coordinates) -> Vec<Coordinates> {
+        self.neighbors(coordinates).collect()
+    }
+
+    /// the tiles of `get_neighbors`, found one at a time without allocating.
+    pub fn neighbors(&self, coordinates: &Coordinates) -> impl Iterator<Item = Coordinates> + '_ {
+        let coordinates = *coordinates;
+        let offsets = self
+            .rules
+            .stencil
+            .offsets(self.rules.topology, coordinates.row);
+
+        // every layer reached gets a slot per offset, after one for the tile
+        // straight across, which only other layers use
+        let slots = offsets.len() + 1;
+        let layers = if self.depth > 1 { 3 } else { 1 };
+
+        let candidate = move |index: usize| {
+            let layer_offset = (index / slots) as i64 - layers as i64 / 2;
+            let (col_offset, row_offset) = match index % slots {
+                0 if layer_offset == 0 => return None,
+                0 => (0, 0),
+                slot => offsets[slot - 1],
+            };
+            let neighbor =
+                Coordinates::new(coordinates.col + col_offset, coordinates.row + row_offset)
+                    .with_layer(coordinates.layer + layer_offset);
+
+            if self.is_endless() {
+                return Some(neighbor);
+            }
+
+            // off the board, or a void tile
+            let neighbor = self.wrap_coordinates(&neighbor);
+            (self.contains(&neighbor) && neighbor != coordinates).then_some(neighbor)
+        };
+
+        (0..slots * layers).filter_map(move |index| {
+            let neighbor = candidate(index)?;
+
+            // on tiny boards several offsets can wrap onto the same tile
+            let repeated =
+                self.rules.wrap && (0..index).any(|earlier| candidate(earlier) == Some(neighbor));
+            (!repeated).then_some(neighbor)
+        })
+    }
+
+    /// the tile `coordinates` stand for on a board that wraps around,
+    /// the coordinates themselves otherwise. layers never wrap.
+    pub fn wrap_coordinates(&self, coordinates: &Coordinates) -> Coordinates {
+        if !self.rules.wrap || self.is_endless() {
+            return *coordinates;
+        }
+
+        Coordinates::new(
+            coordinates.col.rem_euclid(self.width),
+            coordinates.row.rem_euclid(self.height),
+        )
+        .with_layer(coordinates.layer)
+    }
+
+    /// tile of the shown layer at `position`, in world space.
+    pub fn tile_at(&self, position: Vec2) -> Coordinates {
+        Coordinates::from_world_position(position, self.rules.topology).with_layer(self.view_layer)
+    }
+
+    /// how far the layer `offset` layers away from the shown one is drawn
+    /// from the board, to its side. leaves room for the ghost of the
+    /// opposite edges in between.
+    pub fn layer_offset(&self, offset: i64) -> Vec2 {
+        let width = self.rules.topology.board_size(self.width, self.height).x;
+        let gap = (GHOST_TILES + 1) as f32 * TILE_SIZE;
+        Vec2::new((width + gap) * offset as f32, 0.0)
+    }
+
+    /// area covered by the board in world space, see `Topology::tile_center`.
+    /// endless boards have no such area.
+    pub fn world_rect(&self) -> Option<Rect> {
+        if self.is_endless() {
+            return None;
+        }
+
+        let size = self.rules.topology.board_size(self.width, self.height);
+        Some(Rect::new(0.0, -size.y, size.x, 0.0))
+    }
+
+    /// whether there is a tile at `coordinates`. void tiles do not count, and
+    /// in endless mode only tiles of chunks generated so far do.
+    pub fn contains(&self, coordinates: &Coordinates) -> bool {
+        match &self.storage {
+            TileStorage::Bounded(_) => self.in_bounds(coordinates) && !self[*coordinates].is_void(),
+            TileStorage::Endless { chunks, .. } => {
+                coordinates.layer == 0 && chunks.contains_key(&coordinates.chunk())
+            }
+        }
+    }
+
+    /// whether `coordinates` fall within the rectangle of a bounded board,
+    /// void tiles included.
+    pub fn in_bounds(&self, coordinates: &Coordinates) -> bool {
+        !self.is_endless()
+            && (0..self.width).contains(&coordinates.col)
+            && (0..self.height).contains(&coordinates.row)
+            && (0..self.depth).contains(&coordinates.layer)
+    }
+
+    /// smallest rectangle of tiles holding every tile there is, as its top left
+    /// tile and its size.
+    fn tile_bounds(&self) -> (Coordinates, i64, i64) {
+        match &self.storage {
+            TileStorage::Bounded(_) => (Coordinates::new(0, 0), self.width, self.height),
+            TileStorage::Endless { chunks, .. } => {
+                let min = chunks
+                    .keys()
+                    .copied()
+                    .reduce(IVec2::min)
+                    .unwrap_or_default();
+                let max = chunks
+                    .keys()
+                    .copied()
+                    .reduce(IVec2::max)
+                    .unwrap_or_default();
+                let size = (max - min + IVec2::ONE).as_i64vec2() * CHUNK_SIZE;
+                let origin = Coordinates::new(min.x as i64 * CHUNK_SIZE, min.y as i64 * CHUNK_SIZE);
+                (origin, size.x, size.y)
+            }
+        }
+    }
 
-                neighbors.push(Coordinates::new(neighbor_col, neighbor_row));
+    /// every tile there is with its coordinates, leaving out void tiles. in
+    /// endless mode only tiles of chunks generated so far count.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (Coordinates, &Tile)> + '_> {
+        match &self.storage {
+            TileStorage::Bounded(tiles) => {
+                let (width, height) = (self.width, self.height);
+                Box::new(
+                    tiles
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, tile)| !tile.is_void())
+                        .map(move |(index, tile)| {
+                            let index = index as i64;
+                            let coordinates =
+                                Coordinates::new(index % width, index / width % height);
+                            (coordinates.with_layer(index / (width * height)), tile)
+                        }),
+                )
+            }
+            TileStorage::Endless { chunks, .. } => {
+                Box::new(chunks.iter().flat_map(|(chunk, tiles)| {
+                    let origin =
+                        Coordinates::new(chunk.x as i64 * CHUNK_SIZE, chunk.y as i64 * CHUNK_SIZE);
+                    tiles.iter().enumerate().map(move |(index, tile)| {
+                        let index = index as i64;
+                        let coordinates = Coordinates::new(
+                            origin.col + index % CHUNK_SIZE,
+                            origin.row + index / CHUNK_SIZE,
+                        );
+                        (coordinates, tile)
+                    })
+                }))
             }
         }
+    }
+
+    fn tiles(&self) -> Box<dyn Iterator<Item = &Tile> + '_> {
+        match &self.storage {
+            TileStorage::Bounded(tiles) => Box::new(tiles.iter().filter(|tile| !tile.is_void())),
+            TileStorage::Endless { chunks, .. } => Box::new(chunks.values().flatten()),
+        }
+    }
+
+    /// first unflipped, unmarked tile after `from` in reading order, wrapping
+    /// around. only tiles on the layer of `from` are searched.
+    pub fn next_unrevealed(&self, from: &Coordinates) -> Option<Coordinates> {
+        self.find_after(from, |coordinates| self[coordinates].is_hoverable())
+    }
+
+    /// first unflipped, unmarked tile after `from` that touches a flipped tile.
+    pub fn next_frontier(&self, from: &Coordinates) -> Option<Coordinates> {
+        self.find_after(from, |coordinates| {
+            self[coordinates].is_hoverable()
+                && self
+                    .neighbors(&coordinates)
+                    .any(|neighbor| self.contains(&neighbor) && self[neighbor].is_flipped())
+        })
+    }
+
+    fn find_after(
+        &self,
+        from: &Coordinates,
+        predicate: impl Fn(Coordinates) -> bool,
+    ) -> Option<Coordinates> {
+        let (origin, width, height) = self.tile_bounds();
+        let count = width * height;
+        let start = (from.row - origin.row) * width + from.col - origin.col;
+
+        (1..=count)
+            .map(|offset| (start + offset).rem_euclid(count))
+            .map(|index| Coordinates::new(origin.col + index % width, origin.row + index / width))
+            .map(|coordinates| coordinates.with_layer(from.layer))
+            .find(|&coordinates| self.contains(&coordinates) && predicate(coordinates))
+    }
+
+    /// flags placed on the board, counting every flag of a stack.
+    pub fn marked_count(&self) -> usize {
+        self.tiles().map(|tile| tile.flags() as usize).sum()
+    }
+
+    pub fn anti_marked_count(&self) -> usize {
+        self.tiles().filter(|tile| tile.has_anti_flag()).count()
+    }
 
-        neighbors
+    /// tiles holding no mine, which all have to be revealed to clear the board.
+    pub fn safe_count(&self) -> usize {
+        self.tiles().filter(|tile| !tile.contains_mine()).count()
+    }
+
+    /// mines of either kind per tile, on average.
+    pub fn density(&self) -> f64 {
+        match &self.storage {
+            TileStorage::Bounded(_) => {
+                let mines = self.mine_count + self.rules.anti_mines;
+                mines as f64 / self.tiles().count().max(1) as f64
+            }
+            TileStorage::Endless { generator, .. } => generator.density,
+        }
+    }
+
+    /// flipped tiles holding no mine, the score of an endless game.
+    pub fn cleared_count(&self) -> usize {
+        self.tiles()
+            .filter(|tile| tile.is_flipped() && !tile.contains_mine())
+            .count()
+    }
+
+    /// Bechtel's board benchmark value: the fewest clicks that clear the board
+    /// without flags. every opening takes one click, and so does every number
+    /// not uncovered by an opening.
+    pub fn three_bv(&self) -> usize {
+        let mut uncovered = HashSet::new();
+        let mut clicks = 0;
+
+        for (coordinates, tile) in self.iter() {
+            if tile.contains_mine() || tile.number().is_some() || uncovered.contains(&coordinates) {
+                continue;
+            }
+
+            clicks += 1;
+            uncovered.insert(coordinates);
+
+            let mut to_visit = vec![coordinates];
+            while let Some(coordinates) = to_visit.pop() {
+                for neighbor in self.neighbors(&coordinates) {
+                    if !self.contains(&neighbor) || !uncovered.insert(neighbor) {
+                        continue;
+                    }
+
+                    if self[neighbor].number().is_none() {
+                        to_visit.push(neighbor);
+                    }
+                }
+            }
+        }
+
+        let numbers = self
+            .iter()
+            .filter(|(coordinates, tile)| !tile.contains_mine() && !uncovered.contains(coordinates))
+            .count();
+
+        clicks + numbers
     }
 
     pub fn can_reveal_neighbors(&self, coordinates: Coordinates) -> bool {
         let tile = &self[coordinates];
+        if !tile.is_flipped() {
+            return false;
+        }
 
-        let mut mark_count = 0;
-        for neighbour in self.get_neighbors(&coordinates) {
-            if self[neighbour].is_marked() {
-                mark_count += 1;
+        let mark_count = self
+            .neighbors(&coordinates)
+            .map(|neighbor| self[neighbor].marked_charge())
+            .sum();
+
+        tile.number() == Some(mark_count)
+    }
+}
+
+/// position of the tile at `coordinates` in the storage of a bounded board
+/// with layers of `width` x `height` tiles. panics on a row or column off
+/// the board, which would otherwise land on another tile.
+fn tile_index(width: i64, height: i64, coordinates: &Coordinates) -> usize {
+    assert!(
+        (0..width).contains(&coordinates.col) && (0..height).contains(&coordinates.row),
+        "{coordinates:?} is off a {width}x{height} board"
+    );
+    ((coordinates.layer * height + coordinates.row) * width + coordinates.col) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::resources::{FirstClick, Topology};
+
+    /// 9x9 board under `first_click`, mined from a click at `start`.
+    fn mined(first_click: FirstClick, mine_count: u64, start: Coordinates, seed: u64) -> TileMap {
+        let rules = BoardRules {
+            first_click,
+            ..default()
+        };
+        mined_with(rules, mine_count, start, seed)
+    }
+
+    /// 9x9 board under `rules`, mined from a click at `start`.
+    fn mined_with(rules: BoardRules, mine_count: u64, start: Coordinates, seed: u64) -> TileMap {
+        let mut tilemap = TileMap::new(9, 9, 1, mine_count, rules);
+        let mut rng = StdRng::seed_from_u64(seed);
+        tilemap.generate_mines_with(&start, &mut rng).unwrap();
+        tilemap
+    }
+
+    #[test]
+    fn classic_keeps_the_clicked_tile_clear() {
+        for seed in 0..20 {
+            let start = Coordinates::new(seed as i64 % 9, 4);
+            // every other tile holds a mine
+            let tilemap = mined(FirstClick::Classic, 80, start, seed);
+            for (coordinates, tile) in tilemap.iter() {
+                assert_eq!(tile.contains_mine(), coordinates != start);
             }
         }
+    }
 
-        tile.number() == Some(mark_count)
+    #[test]
+    fn opening_keeps_the_clicked_tile_and_its_neighbors_clear() {
+        for seed in 0..20 {
+            for (start, clear) in [(Coordinates::new(4, 4), 9), (Coordinates::new(0, 0), 4)] {
+                let tilemap = mined(FirstClick::Opening, 81 - clear, start, seed);
+                let neighbors = tilemap.get_neighbors(&start);
+                assert!(!tilemap[start].contains_mine());
+                assert!(neighbors.iter().all(|&n| !tilemap[n].contains_mine()));
+                // touching no mine, so the first click opens up
+                assert_eq!(tilemap[start].number(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn radius_policies_keep_their_shape_clear() {
+        let start = Coordinates::new(4, 4);
+        for seed in 0..20 {
+            let tilemap = mined(FirstClick::Chebyshev(1), 72, start, seed);
+            for (coordinates, tile) in tilemap.iter() {
+                let near = coordinates.col.abs_diff(4).max(coordinates.row.abs_diff(4)) <= 1;
+                assert_eq!(tile.contains_mine(), !near);
+            }
+
+            // a diamond of 13 tiles within 2 steps
+            let tilemap = mined(FirstClick::Manhattan(2), 68, start, seed);
+            for (coordinates, tile) in tilemap.iter() {
+                let near = coordinates.manhattan_distance(&start) <= 2;
+                assert_eq!(tile.contains_mine(), !near);
+            }
+        }
+    }
+
+    #[test]
+    fn radius_policies_reach_across_wrapped_edges() {
+        let start = Coordinates::new(0, 0);
+        let rules = BoardRules {
+            first_click: FirstClick::Chebyshev(1),
+            wrap: true,
+            ..default()
+        };
+        for seed in 0..20 {
+            let tilemap = mined_with(rules.clone(), 72, start, seed);
+            for (coordinates, tile) in tilemap.iter() {
+                let near =
+                    [8, 0, 1].contains(&coordinates.col) && [8, 0, 1].contains(&coordinates.row);
+                assert_eq!(tile.contains_mine(), !near);
+            }
+        }
+    }
+
+    #[test]
+    fn radius_policies_count_hex_steps() {
+        let start = Coordinates::new(4, 4);
+        for first_click in [FirstClick::Chebyshev(1), FirstClick::Manhattan(1)] {
+            let rules = BoardRules {
+                first_click,
+                topology: Topology::Hex,
+                ..default()
+            };
+            for seed in 0..20 {
+                // the clicked hexagon and its six neighbors
+                let tilemap = mined_with(rules.clone(), 74, start, seed);
+                let neighbors = tilemap.get_neighbors(&start);
+                for (coordinates, tile) in tilemap.iter() {
+                    let near = coordinates == start || neighbors.contains(&coordinates);
+                    assert_eq!(tile.contains_mine(), !near);
+                }
+            }
+        }
+    }
+
+    /// whether `mine_count` mines and `anti_mines` anti-mines fit on a 9x9
+    /// board with a classic first click, which leaves 80 tiles.
+    fn fits(mine_count: u64, anti_mines: u64, mines_per_tile: u8) -> bool {
+        let rules = BoardRules {
+            first_click: FirstClick::Classic,
+            mines_per_tile,
+            anti_mines,
+            ..default()
+        };
+        let mut tilemap = TileMap::new(9, 9, 1, mine_count, rules);
+        let mut rng = StdRng::seed_from_u64(0);
+        let result = tilemap.generate_mines_with(&Coordinates::new(4, 4), &mut rng);
+        if result.is_err() {
+            assert!(tilemap.tiles().all(|tile| tile.charge() == 0));
+        }
+        result.is_ok()
+    }
+
+    #[test]
+    fn mines_fit_up_to_the_tiles_left_by_the_first_click() {
+        assert!(fits(80, 0, 1));
+        assert!(!fits(81, 0, 1));
+
+        assert!(fits(160, 0, 2));
+        assert!(!fits(161, 0, 2));
+
+        // anti-mines take a whole tile each
+        assert!(fits(70, 10, 1));
+        assert!(!fits(71, 10, 1));
+        assert!(fits(0, 80, 1));
+        assert!(!fits(0, 81, 1));
+    }
+
+    #[test]
+    fn none_may_mine_the_clicked_tile() {
+        let start = Coordinates::new(4, 4);
+        let tilemap = mined(FirstClick::None, 81, start, 0);
+        assert!(tilemap.tiles().all(|tile| tile.contains_mine()));
     }
 }
@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+
+use super::{Coordinates, Glyph, Shade};
+use crate::TILE_SIZE;
+
+const SQUARE_NEIGHBORS: [(i64, i64); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+const EVEN_ROW_HEX_NEIGHBORS: [(i64, i64); 6] =
+    [(-1, -1), (0, -1), (-1, 0), (1, 0), (-1, 1), (0, 1)];
+const ODD_ROW_HEX_NEIGHBORS: [(i64, i64); 6] = [(0, -1), (1, -1), (-1, 0), (1, 0), (0, 1), (1, 1)];
+
+/// shape of the tiles and how they fit together.
+///
+/// hex boards use pointy topped hexagons in "odd-r" offset coordinates: every
+/// odd row is shifted right by half a tile, so `col` and `row` keep addressing
+/// a rectangular board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Topology {
+    #[default]
+    Square,
+    Hex,
+}
+
+impl Topology {
+    pub const ALL: [Topology; 2] = [Self::Square, Self::Hex];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|&topology| topology == self)
+            .unwrap();
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Square => "square",
+            Self::Hex => "hex",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|topology| topology.name() == name)
+    }
+
+    /// `(col, row)` offsets of the tiles touching a tile in `row`.
+    pub fn neighbor_offsets(self, row: i64) -> &'static [(i64, i64)] {
+        match (self, row.rem_euclid(2)) {
+            (Self::Square, _) => &SQUARE_NEIGHBORS,
+            (Self::Hex, 0) => &EVEN_ROW_HEX_NEIGHBORS,
+            (Self::Hex, _) => &ODD_ROW_HEX_NEIGHBORS,
+        }
+    }
+
+    /// checkerboard on square boards. hexagons need three shades so that no
+    /// two neighbors share one. the pattern shifts from one layer to the next,
+    /// so layers shown side by side tell apart.
+    pub fn shade(self, coordinates: &Coordinates) -> Shade {
+        let layer = coordinates.layer;
+        let index = match self {
+            Self::Square => (coordinates.row + coordinates.col + layer).rem_euclid(2),
+            Self::Hex => {
+                let (q, r) = axial(coordinates);
+                (q - r + layer).rem_euclid(3)
+            }
+        };
+
+        match index {
+            0 => Shade::Light,
+            1 => Shade::Dark,
+            _ => Shade::Medium,
+        }
+    }
+
+    /// center of a tile in world space. the top left corner of tile `(0, 0)`
+    /// sits on the origin and rows grow downwards.
+    pub fn tile_center(self, coordinates: &Coordinates) -> Vec2 {
+        let col = coordinates.col as f32;
+        let row = coordinates.row as f32;
+
+        match self {
+            Self::Square => Vec2::new(col + 0.5, -row - 0.5) * TILE_SIZE,
+            Self::Hex => {
+                let shift = coordinates.row.rem_euclid(2) as f32 * 0.5;
+                Vec2::new(
+                    (col + 0.5 + shift) * TILE_SIZE,
+                    -(row * hex_row_height() + hex_radius()),
+                )
+            }
+        }
+    }
+
+    /// tile containing `position`, in world space. the inverse of `tile_center`.
+    pub fn tile_at(self, position: Vec2) -> Coordinates {
+        match self {
+            Self::Square => Coordinates::new(
+                (position.x / TILE_SIZE).floor() as i64,
+                (-position.y / TILE_SIZE).floor() as i64,
+            ),
+            Self::Hex => {
+                let offset = position - self.tile_center(&Coordinates::new(0, 0));
+                let (x, y) = (offset.x, -offset.y);
+                let q = (x * 3f32.sqrt() / 3.0 - y / 3.0) / hex_radius();
+                let r = (y * 2.0 / 3.0) / hex_radius();
+                let (q, r) = round_axial(q, r);
+                Coordinates::new(q + (r - r.rem_euclid(2)) / 2, r)
+            }
+        }
+    }
+
+    /// size of the area covered by a board of `width` x `height` tiles.
+    pub fn board_size(self, width: i64, height: i64) -> Vec2 {
+        match self {
+            Self::Square => Vec2::new(width as f32, height as f32) * TILE_SIZE,
+            Self::Hex => {
+                let shift = if height > 1 { 0.5 } else { 0.0 };
+                Vec2::new(
+                    (width as f32 + shift) * TILE_SIZE,
+                    (height - 1).max(0) as f32 * hex_row_height() + 2.0 * hex_radius(),
+                )
+            }
+        }
+    }
+
+    /// steps between two tiles of a layer. square boards count a diagonal as
+    /// one step only with `diagonals`, hexagons are all a single step apart.
+    pub fn steps(self, from: &Coordinates, to: &Coordinates, diagonals: bool) -> u64 {
+        match self {
+            Self::Square => {
+                let cols = from.col.abs_diff(to.col);
+                let rows = from.row.abs_diff(to.row);
+                if diagonals {
+                    cols.max(rows)
+                } else {
+                    cols + rows
+                }
+            }
+            Self::Hex => {
+                let (from_q, from_r) = axial(from);
+                let (to_q, to_r) = axial(to);
+                let (dq, dr) = (from_q - to_q, from_r - to_r);
+                (dq.unsigned_abs() + dr.unsigned_abs() + (dq + dr).unsigned_abs()) / 2
+            }
+        }
+    }
+
+    /// atlas glyph filling a tile, and the size of the square it is drawn in.
+    pub fn tile_fill(self) -> (Glyph, f32) {
+        match self {
+            Self::Square => (Glyph::Solid, TILE_SIZE),
+            Self::Hex => (Glyph::Hexagon, 2.0 * hex_radius()),
+        }
+    }
+
+    /// mesh of a single tile, centered on the origin.
+    pub fn tile_mesh(self) -> Mesh {
+        match self {
+            Self::Square => Rectangle::new(TILE_SIZE, TILE_SIZE).into(),
+            Self::Hex => RegularPolygon::new(hex_radius(), 6).into(),
+        }
+    }
+}
+
+/// distance from the center of a hexagon to its corners, so that it is
+/// `TILE_SIZE` wide.
+fn hex_radius() -> f32 {
+    TILE_SIZE / 3f32.sqrt()
+}
+
+/// vertical distance between the centers of two rows of hexagons.
+fn hex_row_height() -> f32 {
+    hex_radius() * 1.5
+}
+
+/// axial hex coordinates of an odd-r offset tile.
+fn axial(coordinates: &Coordinates) -> (i64, i64) {
+    let row = coordinates.row;
+    (coordinates.col - (row - row.rem_euclid(2)) / 2, row)
+}
+
+/// nearest hexagon to fractional axial coordinates, rounding in cube
+/// coordinates and fixing up the component that moved the most.
+fn round_axial(q: f32, r: f32) -> (i64, i64) {
+    let s = -q - r;
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+
+    (rq as i64, rr as i64)
+}
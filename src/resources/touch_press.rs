@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use super::Coordinates;
+
+/// single finger press that may turn into a tap or a long-press flag.
+#[derive(Debug, Resource, Default)]
+pub struct TouchPress {
+    pub touch: Option<u64>,
+    pub coordinates: Coordinates,
+    pub started: f32,
+    /// fraction of the long-press duration held so far, in `0.0..=1.0`.
+    pub progress: f32,
+    pub flagged: bool,
+}
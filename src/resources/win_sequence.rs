@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use super::Coordinates;
+
+/// progress of the celebration played once the board is cleared,
+/// and the results it ends on.
+#[derive(Debug, Resource, Default)]
+pub struct WinSequence {
+    /// time since the board was cleared, in seconds.
+    pub elapsed: f32,
+    /// mines still unflagged with the time they get flagged at, latest first.
+    pub flags: Vec<(f32, Coordinates)>,
+    /// tiles with the time the particle wave passes over them, latest first.
+    pub wave: Vec<(f32, Coordinates)>,
+    pub time: f32,
+    pub three_bv: usize,
+    /// record of the board before this game, if it was played before.
+    pub previous_best: Option<f32>,
+    pub new_best: bool,
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::events::{NewGameEvent, TileChange, TileChangedEvent};
+use crate::resources::{Settings, TileAnimationKind, TileAnimations};
+
+/// delay per tile of distance from the click before a tile of an opening flips,
+/// in seconds, so openings ripple outwards.
+const CASCADE_SECS_PER_TILE: f32 = 0.03;
+/// longest cascade delay, so huge openings do not take ages to show.
+const MAX_CASCADE_SECS: f32 = 0.6;
+
+pub fn start_tile_animations(
+    mut tile_changed_events: EventReader<TileChangedEvent>,
+    mut new_game_events: EventReader<NewGameEvent>,
+    mut animations: ResMut<TileAnimations>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    if new_game_events.read().count() > 0 {
+        animations.tiles.clear();
+    }
+
+    if !settings.animations {
+        tile_changed_events.clear();
+        return;
+    }
+
+    let now = time.elapsed_secs();
+
+    for &TileChangedEvent {
+        coordinates,
+        change,
+    } in tile_changed_events.read()
+    {
+        match change {
+            TileChange::Revealed { origin } => {
+                let delay =
+                    (origin.distance(&coordinates) * CASCADE_SECS_PER_TILE).min(MAX_CASCADE_SECS);
+                animations.start(coordinates, TileAnimationKind::Flip, now + delay);
+            }
+            TileChange::Marked => animations.start(coordinates, TileAnimationKind::Pop, now),
+            TileChange::Unmarked => {
+                animations.tiles.remove(&coordinates);
+            }
+        }
+    }
+}
+
+/// forgets finished animations, once their chunks were drawn in their final state.
+pub fn finish_tile_animations(mut animations: ResMut<TileAnimations>, time: Res<Time>) {
+    let now = time.elapsed_secs();
+    animations
+        .tiles
+        .retain(|_, animation| !animation.is_finished(now));
+}
@@ -0,0 +1,97 @@
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+use crate::components::Music;
+use crate::events::{
+    Effect, RevealNeighborsEvent, SpawnEffectsEvent, TileChange, TileChangedEvent,
+};
+use crate::resources::{Menu, Settings, SoundClip, SoundHandles};
+
+/// how much each doubling of an opening's size lowers the reveal sound.
+const REVEAL_PITCH_DROP: f32 = 0.15;
+
+pub fn setup_audio(
+    mut commands: Commands,
+    mut clips: ResMut<Assets<SoundClip>>,
+    settings: Res<Settings>,
+) {
+    let handles = SoundHandles::new(&mut clips);
+
+    commands.spawn((
+        Music,
+        AudioPlayer(handles.music.clone()),
+        PlaybackSettings::LOOP.with_volume(Volume::new(settings.effective_music_volume())),
+    ));
+
+    commands.insert_resource(handles);
+}
+
+/// plays sounds for what the game already reports through its events,
+/// so gameplay systems never deal with audio.
+pub fn play_sound_effects(
+    mut commands: Commands,
+    mut effect_events: EventReader<SpawnEffectsEvent>,
+    mut tile_changed_events: EventReader<TileChangedEvent>,
+    mut reveal_neighbors_events: EventReader<RevealNeighborsEvent>,
+    menu: Res<Menu>,
+    settings: Res<Settings>,
+    sounds: Res<SoundHandles>,
+) {
+    let mut revealed = 0;
+    let mut played = Vec::new();
+
+    for event in effect_events.read() {
+        match event.effect {
+            Effect::RevealBurst => revealed += 1,
+            Effect::Explosion => played.push((&sounds.explosion, 1.0)),
+            Effect::Confetti => played.push((&sounds.win, 1.0)),
+            Effect::Sparkle => {}
+        }
+    }
+
+    // one sound per opening, deeper the more tiles it uncovers
+    if revealed > 0 {
+        let speed = 1.0 / (1.0 + REVEAL_PITCH_DROP * (revealed as f32).log2());
+        played.push((&sounds.reveal, speed));
+    }
+
+    for event in tile_changed_events.read() {
+        match event.change {
+            TileChange::Marked => played.push((&sounds.flag_placed, 1.0)),
+            TileChange::Unmarked => played.push((&sounds.flag_removed, 1.0)),
+            TileChange::Revealed { .. } => {}
+        }
+    }
+
+    if reveal_neighbors_events.read().count() > 0 {
+        played.push((&sounds.chord, 1.0));
+    }
+
+    if menu.is_changed() && !menu.is_added() {
+        played.push((&sounds.click, 1.0));
+    }
+
+    let volume = settings.effective_sfx_volume();
+    if volume <= 0.0 {
+        return;
+    }
+
+    for (clip, speed) in played {
+        commands.spawn((
+            AudioPlayer(clip.clone()),
+            PlaybackSettings::DESPAWN
+                .with_volume(Volume::new(volume))
+                .with_speed(speed),
+        ));
+    }
+}
+
+pub fn update_music_volume(settings: Res<Settings>, music: Query<&AudioSink, With<Music>>) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for sink in music.iter() {
+        sink.set_volume(settings.effective_music_volume());
+    }
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::events::{FlipTileEvent, GameStartEvent, ToggleMarkEvent};
+use crate::resources::{
+    solve, Autoplay, BotMove, Cursor, CursorSource, GameState, Settings, TileMap,
+};
+
+/// plays the board while autoplay is on, sending the same events as the
+/// player's own input so the game reacts to the bot as it would to them.
+pub fn run_autoplay(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    gamestate: Res<GameState>,
+    mut autoplay: ResMut<Autoplay>,
+    mut tilemap: ResMut<TileMap>,
+    mut cursor: ResMut<Cursor>,
+    mut game_start_events: EventWriter<GameStartEvent>,
+    mut flip_events: EventWriter<FlipTileEvent>,
+    mut mark_events: EventWriter<ToggleMarkEvent>,
+) {
+    // moves found for an earlier game are no use on a new one
+    let pending = *gamestate == GameState::Pending;
+    if !settings.autoplay || gamestate.is_over() || pending {
+        autoplay.moves.clear();
+    }
+    if pending {
+        autoplay.played = false;
+    }
+    if !settings.autoplay || gamestate.is_over() {
+        return;
+    }
+
+    let period = Duration::from_secs_f32(1.0 / settings.autoplay_speed);
+    if autoplay.timer.duration() != period {
+        autoplay.timer = Timer::new(period, TimerMode::Repeating);
+    }
+    autoplay.timer.tick(time.delta());
+
+    // the board only changes once the events are handled, so it is solved
+    // at most once a frame
+    let mut solved = false;
+    for _ in 0..autoplay.timer.times_finished_this_tick() {
+        if autoplay.moves.is_empty() {
+            if solved {
+                return;
+            }
+            autoplay.moves.extend(solve(&tilemap));
+            solved = true;
+        }
+
+        let Some(bot_move) = autoplay.moves.pop_front() else {
+            return;
+        };
+
+        // the player may have played the tile in the meantime
+        let coordinates = bot_move.coordinates();
+        if tilemap.contains(&coordinates) && !tilemap[coordinates].is_hoverable() {
+            continue;
+        }
+
+        match bot_move {
+            BotMove::Reveal(_) | BotMove::Guess(_) => {
+                if pending {
+                    game_start_events.send(GameStartEvent::new(coordinates));
+                }
+                flip_events.send(FlipTileEvent::new(coordinates));
+            }
+            BotMove::Mark(_, charge) => {
+                // an anti-flag comes after the last flag of a full stack
+                let toggles = match charge < 0 {
+                    true => tilemap.rules.mines_per_tile as i32 + 1,
+                    false => charge,
+                };
+                for _ in 0..toggles {
+                    mark_events.send(ToggleMarkEvent { coordinates });
+                }
+            }
+        }
+
+        autoplay.played = true;
+        *cursor = Cursor {
+            coordinates,
+            source: CursorSource::Keyboard,
+            visible: true,
+        };
+        tilemap.view_layer = coordinates.layer;
+
+        // the first reveal places the mines, which the next moves depend on
+        if pending {
+            return;
+        }
+    }
+}
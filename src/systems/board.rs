@@ -0,0 +1,216 @@
+use bevy::core_pipeline::bloom::Bloom;
+use bevy::prelude::*;
+
+use crate::components::Chunk;
+use crate::events::{
+    BoardClearedEvent, Effect, FlipTileEvent, GameStartEvent, MineHitEvent, NewGameEvent,
+    RemoveMarkEvent, RevealNeighborsEvent, SpawnEffectsEvent, TileAction, TileActionEvent,
+    TileChange, TileChangedEvent, ToggleMarkEvent,
+};
+use crate::resources::{GameClock, GameState, Menu, MenuItem, Settings, TileMap};
+use crate::BLOOM_INTENSITY;
+
+pub fn handle_new_game(
+    mut commands: Commands,
+    mut new_game_events: EventReader<NewGameEvent>,
+    chunks: Query<Entity, With<Chunk>>,
+    settings: Res<Settings>,
+    mut gamestate: ResMut<GameState>,
+    mut clock: ResMut<GameClock>,
+    mut tilemap: ResMut<TileMap>,
+) {
+    if new_game_events.read().count() == 0 {
+        return;
+    }
+
+    *tilemap = settings.new_tilemap();
+    *gamestate = GameState::Pending;
+    *clock = GameClock::default();
+
+    // chunks of the new board are spawned again as they come into view
+    for entity in chunks.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn handle_tile_action(
+    mut tile_actions: EventReader<TileActionEvent>,
+    mut flip_events: EventWriter<FlipTileEvent>,
+    mut mark_events: EventWriter<ToggleMarkEvent>,
+    mut remove_mark_events: EventWriter<RemoveMarkEvent>,
+    mut game_start_events: EventWriter<GameStartEvent>,
+    mut auto_reveal_events: EventWriter<RevealNeighborsEvent>,
+    gamestate: Res<GameState>,
+    tilemap: Res<TileMap>,
+) {
+    if gamestate.is_over() {
+        tile_actions.clear();
+        return;
+    }
+
+    let mut pending = *gamestate == GameState::Pending;
+
+    for &TileActionEvent {
+        action,
+        coordinates,
+    } in tile_actions.read()
+    {
+        if !tilemap.contains(&coordinates) {
+            continue;
+        }
+
+        match action {
+            TileAction::Reveal => {
+                if pending {
+                    game_start_events.send(GameStartEvent::new(coordinates));
+                    pending = false;
+                }
+
+                flip_events.send(FlipTileEvent { coordinates });
+            }
+            TileAction::Flag => {
+                mark_events.send(ToggleMarkEvent { coordinates });
+            }
+            TileAction::Unflag => {
+                remove_mark_events.send(RemoveMarkEvent { coordinates });
+            }
+            TileAction::Chord => {
+                auto_reveal_events.send(RevealNeighborsEvent { coordinates });
+            }
+        }
+    }
+}
+
+/// places the mines on the first reveal. when they do not fit, the game does
+/// not start and the menu opens on the board settings with the reason.
+pub fn handle_game_start(
+    mut game_start_events: EventReader<GameStartEvent>,
+    mut bloom: Single<&mut Bloom>,
+    mut gamestate: ResMut<GameState>,
+    mut tilemap: ResMut<TileMap>,
+    mut menu: ResMut<Menu>,
+) {
+    let Some(event) = game_start_events.read().next() else {
+        return;
+    };
+
+    let mouse_coordinates = event.mouse_coordinates();
+    if let Err(message) = tilemap.generate_mines(&mouse_coordinates) {
+        menu.show_error(format!("{message}. Change the board and start a new game."));
+        menu.select(MenuItem::MineCount);
+        return;
+    }
+    *gamestate = GameState::Ongoing;
+
+    bloom.intensity = BLOOM_INTENSITY;
+    bloom.low_frequency_boost = 0.35;
+}
+
+pub fn handle_flip_tile(
+    mut flip_event_reader: EventReader<FlipTileEvent>,
+    mut flip_effects_writer: EventWriter<SpawnEffectsEvent>,
+    mut tile_changed_events: EventWriter<TileChangedEvent>,
+    mut mine_hit_events: EventWriter<MineHitEvent>,
+    mut board_cleared_events: EventWriter<BoardClearedEvent>,
+    mut gamestate: ResMut<GameState>,
+    mut tilemap: ResMut<TileMap>,
+) {
+    // a game that could not start has no mines to flip
+    if *gamestate == GameState::Pending {
+        flip_event_reader.clear();
+        return;
+    }
+
+    let mut last_flipped = None;
+
+    for event in flip_event_reader.read() {
+        let origin = event.coordinates;
+        for coordinates in tilemap.reveal(origin) {
+            tile_changed_events.send(TileChangedEvent {
+                coordinates,
+                change: TileChange::Revealed { origin },
+            });
+
+            if tilemap[coordinates].contains_mine() {
+                flip_effects_writer.send(SpawnEffectsEvent {
+                    coordinates,
+                    effect: Effect::Explosion,
+                });
+                mine_hit_events.send(MineHitEvent { coordinates });
+                *gamestate = GameState::Lost;
+                return;
+            }
+
+            flip_effects_writer.send(SpawnEffectsEvent {
+                coordinates,
+                effect: Effect::RevealBurst,
+            });
+            last_flipped = Some(coordinates);
+        }
+    }
+
+    let Some(coordinates) = last_flipped else {
+        return;
+    };
+
+    // endless boards can never be cleared
+    if !tilemap.is_endless() && tilemap.cleared_count() == tilemap.safe_count() {
+        *gamestate = GameState::Won;
+        board_cleared_events.send(BoardClearedEvent { coordinates });
+    }
+}
+
+/// toggles flags, or stacks them on boards with several mines per tile.
+pub fn handle_toggle_mark(
+    mut mark_events: EventReader<ToggleMarkEvent>,
+    mut remove_mark_events: EventReader<RemoveMarkEvent>,
+    mut tile_changed_events: EventWriter<TileChangedEvent>,
+    mut tilemap: ResMut<TileMap>,
+) {
+    let limit = tilemap.rules.mines_per_tile;
+    let anti_flags = tilemap.rules.anti_mines > 0;
+    let added = mark_events.read().map(|event| (event.coordinates, true));
+    let removed = remove_mark_events
+        .read()
+        .map(|event| (event.coordinates, false));
+
+    for (coordinates, add) in added.chain(removed) {
+        let tile = &mut tilemap[coordinates];
+        let (flags, anti_flag) = (tile.flags(), tile.has_anti_flag());
+
+        let changed = match add {
+            true => tile.add_flag(limit, anti_flags),
+            false => tile.remove_flag(),
+        };
+        if !changed {
+            continue;
+        }
+
+        let placed = tile.flags() > flags || (tile.has_anti_flag() && !anti_flag);
+        let change = match placed {
+            true => TileChange::Marked,
+            false => TileChange::Unmarked,
+        };
+        tile_changed_events.send(TileChangedEvent {
+            coordinates,
+            change,
+        });
+    }
+}
+
+pub fn handle_auto_reveal(
+    mut reveal_neighbors_events: EventReader<RevealNeighborsEvent>,
+    mut flip_events: EventWriter<FlipTileEvent>,
+    tilemap: Res<TileMap>,
+) {
+    for event in reveal_neighbors_events.read() {
+        let coordinates = event.coordinates;
+        if !tilemap.can_reveal_neighbors(coordinates) {
+            continue;
+        }
+
+        for neighbor in tilemap.get_neighbors(&coordinates) {
+            flip_events.send(FlipTileEvent::new(neighbor));
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use bevy::core_pipeline::bloom::Bloom;
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use bevy::window::{WindowResized, WindowScaleFactorChanged};
+
+use crate::components::MainCamera;
+use crate::events::NewGameEvent;
+use crate::resources::{Action, CameraShake, Cursor, CursorSource, MouseDrag, Settings, TileMap};
+use crate::{GHOST_TILES, HUD_HEIGHT, MIN_CAMERA_SCALE, TILE_SIZE};
+
+use super::input::{cursor_world_position, shoulder_pressed, STICK_THRESHOLD};
+
+pub fn setup_camera(mut commands: Commands) {
+    commands.spawn((
+        MainCamera,
+        Camera2d,
+        Camera {
+            hdr: true,
+            ..default()
+        },
+        Tonemapping::TonyMcMapface,
+        Bloom::default(),
+    ));
+}
+
+/// part of the window left for the board below the HUD, in logical pixels.
+fn board_area(window: &Window) -> Vec2 {
+    Vec2::new(window.width(), window.height() - HUD_HEIGHT).max(Vec2::ONE)
+}
+
+/// farthest zoom on an endless board, which bounds how many chunks are in view.
+const ENDLESS_MAX_SCALE: f32 = 4.0;
+
+/// camera scale at which the whole board fits below the HUD, if it has edges.
+fn fit_scale(window: &Window, tilemap: &TileMap) -> Option<f32> {
+    let board = tilemap.world_rect()?.size();
+    Some((board / board_area(window)).max_element())
+}
+
+/// offset from the world point shown in the middle of the board area
+/// to the camera position, which sits in the middle of the whole window.
+fn hud_offset(scale: f32) -> Vec2 {
+    Vec2::new(0.0, HUD_HEIGHT / 2.0 * scale)
+}
+
+fn fit(
+    window: &Window,
+    tilemap: &TileMap,
+    transform: &mut Transform,
+    projection: &mut OrthographicProjection,
+) {
+    // endless boards have no edges to fit, so show the area around the origin
+    projection.scale = fit_scale(window, tilemap).unwrap_or(1.0);
+    let board_center = tilemap
+        .world_rect()
+        .map_or(Vec2::ZERO, |board| board.center());
+
+    let center = board_center + hud_offset(projection.scale);
+    transform.translation = center.extend(transform.translation.z);
+}
+
+/// scales the view by `factor` while keeping `focus` at the same place on screen.
+fn zoom_around(
+    transform: &mut Transform,
+    projection: &mut OrthographicProjection,
+    focus: Vec2,
+    factor: f32,
+) {
+    let center = transform.translation.truncate();
+    let center = focus + (center - focus) * factor;
+    transform.translation = center.extend(transform.translation.z);
+    projection.scale *= factor;
+}
+
+pub fn fit_camera(
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    camera: Single<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+    tilemap: Res<TileMap>,
+) {
+    let (mut transform, mut projection) = camera.into_inner();
+    fit(
+        q_windows.single(),
+        &tilemap,
+        &mut transform,
+        &mut projection,
+    );
+}
+
+/// refits the board whenever the window size or its DPI scale factor changes,
+/// and when a new game starts.
+pub fn refit_camera(
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    mut resize_events: EventReader<WindowResized>,
+    mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
+    mut new_game_events: EventReader<NewGameEvent>,
+    camera: Single<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+    tilemap: Res<TileMap>,
+) {
+    let resized = resize_events.read().count() > 0;
+    let rescaled = scale_factor_events.read().count() > 0;
+    let restarted = new_game_events.read().count() > 0;
+    if !resized && !rescaled && !restarted {
+        return;
+    }
+
+    let (mut transform, mut projection) = camera.into_inner();
+    fit(
+        q_windows.single(),
+        &tilemap,
+        &mut transform,
+        &mut projection,
+    );
+}
+
+/// zoom factor applied per line of mouse wheel scrolling.
+const WHEEL_ZOOM_STEP: f32 = 1.1;
+/// pixels of a high resolution scroll that count as one line.
+const PIXELS_PER_LINE: f32 = 100.0;
+
+pub fn handle_mouse_wheel(
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    mut wheel_events: EventReader<MouseWheel>,
+    keys: Res<ButtonInput<KeyCode>>,
+    tilemap: Res<TileMap>,
+    camera: Single<
+        (
+            &Camera,
+            &GlobalTransform,
+            &mut Transform,
+            &mut OrthographicProjection,
+        ),
+        With<MainCamera>,
+    >,
+) {
+    let lines: f32 = wheel_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        })
+        .sum();
+
+    // shift scrolls through the layers of a layered board instead
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if lines == 0.0 || (shift && tilemap.depth > 1) {
+        return;
+    }
+
+    let (camera, camera_transform, mut transform, mut projection) = camera.into_inner();
+    let focus = cursor_world_position(q_windows.single(), camera, camera_transform)
+        .unwrap_or(transform.translation.truncate());
+
+    zoom_around(
+        &mut transform,
+        &mut projection,
+        focus,
+        WHEEL_ZOOM_STEP.powf(-lines),
+    );
+}
+
+/// how far, in pixels, the mouse must travel with the left button held
+/// before the press becomes a pan instead of a reveal.
+const DRAG_SLOP: f32 = 6.0;
+
+pub fn handle_mouse_pan(
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut drag: ResMut<MouseDrag>,
+    camera: Single<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    let position = q_windows.single().cursor_position();
+
+    if buttons.just_pressed(MouseButton::Left) {
+        *drag = MouseDrag {
+            last: position,
+            travelled: 0.0,
+            dragging: false,
+        };
+        return;
+    }
+
+    if !buttons.pressed(MouseButton::Left) {
+        drag.last = None;
+        return;
+    }
+
+    let (Some(last), Some(position)) = (drag.last, position) else {
+        return;
+    };
+
+    let delta = position - last;
+    drag.last = Some(position);
+    drag.travelled += delta.length();
+
+    if drag.travelled < DRAG_SLOP {
+        return;
+    }
+
+    drag.dragging = true;
+
+    let (mut transform, projection) = camera.into_inner();
+    // screen space grows downwards, world space upwards
+    transform.translation += Vec3::new(-delta.x, delta.y, 0.0) * projection.scale;
+}
+
+/// zoom factor applied per press of the zoom keys.
+const KEY_ZOOM_STEP: f32 = 1.25;
+
+pub fn handle_camera_keys(
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<Settings>,
+    cursor: Res<Cursor>,
+    camera: Single<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+    tilemap: Res<TileMap>,
+) {
+    let bindings = &settings.key_bindings;
+    let (mut transform, mut projection) = camera.into_inner();
+
+    if bindings.just_pressed(Action::FitToWindow, &keys) {
+        fit(
+            q_windows.single(),
+            &tilemap,
+            &mut transform,
+            &mut projection,
+        );
+        return;
+    }
+
+    let factor = match (
+        bindings.just_pressed(Action::ZoomIn, &keys),
+        bindings.just_pressed(Action::ZoomOut, &keys),
+    ) {
+        (true, false) => 1.0 / KEY_ZOOM_STEP,
+        (false, true) => KEY_ZOOM_STEP,
+        _ => return,
+    };
+
+    // zoom towards the keyboard cursor when it is in use, the screen center otherwise
+    let focus = match cursor.source {
+        CursorSource::Keyboard | CursorSource::Gamepad => cursor
+            .coordinates
+            .get_transform(tilemap.rules.topology, 0.0)
+            .translation
+            .truncate(),
+        CursorSource::Mouse | CursorSource::Touch => transform.translation.truncate(),
+    };
+
+    zoom_around(&mut transform, &mut projection, focus, factor);
+}
+
+/// zoom speed of the shoulder buttons, in scale factors per second.
+const GAMEPAD_ZOOM_SPEED: f32 = 1.5;
+/// pan speed of the sticks, in screen pixels per second.
+const GAMEPAD_PAN_SPEED: f32 = 600.0;
+
+/// shoulder buttons zoom, or pan with the left stick while it is pushed. the
+/// right stick pans on its own.
+pub fn handle_gamepad_camera(
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    camera: Single<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    let (mut transform, mut projection) = camera.into_inner();
+    let dt = time.delta_secs();
+
+    for gamepad in gamepads.iter() {
+        let mut pan = gamepad.right_stick();
+
+        if shoulder_pressed(gamepad) && gamepad.left_stick().length() >= STICK_THRESHOLD {
+            pan = gamepad.left_stick();
+        } else {
+            if gamepad.pressed(GamepadButton::RightTrigger) {
+                projection.scale *= 1.0 - GAMEPAD_ZOOM_SPEED * dt;
+            }
+            if gamepad.pressed(GamepadButton::LeftTrigger) {
+                projection.scale *= 1.0 + GAMEPAD_ZOOM_SPEED * dt;
+            }
+        }
+
+        if pan.length() >= STICK_THRESHOLD {
+            transform.translation += (pan * GAMEPAD_PAN_SPEED * projection.scale * dt).extend(0.0);
+        }
+    }
+}
+
+pub fn handle_touch_camera(
+    touches: Res<Touches>,
+    camera: Single<
+        (
+            &Camera,
+            &GlobalTransform,
+            &mut Transform,
+            &mut OrthographicProjection,
+        ),
+        With<MainCamera>,
+    >,
+) {
+    let mut active = touches.iter();
+    let (Some(first), Some(second), None) = (active.next(), active.next(), active.next()) else {
+        return;
+    };
+
+    let (camera, camera_transform, mut transform, mut projection) = camera.into_inner();
+
+    let midpoint = (first.position() + second.position()) / 2.0;
+    let previous_midpoint = (first.previous_position() + second.previous_position()) / 2.0;
+    let delta = midpoint - previous_midpoint;
+
+    // screen space grows downwards, world space upwards
+    transform.translation += Vec3::new(-delta.x, delta.y, 0.0) * projection.scale;
+
+    let distance = first.position().distance(second.position());
+    let previous_distance = first
+        .previous_position()
+        .distance(second.previous_position());
+    if distance == 0.0 || previous_distance == 0.0 {
+        return;
+    }
+
+    let focus = camera
+        .viewport_to_world_2d(camera_transform, midpoint)
+        .unwrap_or(transform.translation.truncate());
+
+    zoom_around(
+        &mut transform,
+        &mut projection,
+        focus,
+        previous_distance / distance,
+    );
+}
+
+/// pans the camera so a keyboard or gamepad cursor never leaves the screen,
+/// then limits zoom and keeps the view over the board, if it has edges.
+pub fn update_camera(
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    cursor: Res<Cursor>,
+    camera: Single<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+    tilemap: Res<TileMap>,
+) {
+    let window = q_windows.single();
+    let (mut transform, mut projection) = camera.into_inner();
+
+    let max_scale = fit_scale(window, &tilemap)
+        .unwrap_or(ENDLESS_MAX_SCALE)
+        .max(MIN_CAMERA_SCALE);
+    projection.scale = projection.scale.clamp(MIN_CAMERA_SCALE, max_scale);
+
+    // work with the point shown in the middle of the board area, below the HUD
+    let offset = hud_offset(projection.scale);
+    let mut view_center = transform.translation.truncate() - offset;
+    let half_view = board_area(window) / 2.0 * projection.scale;
+
+    let stepped = matches!(
+        cursor.source,
+        CursorSource::Keyboard | CursorSource::Gamepad
+    );
+    if cursor.is_changed() && stepped {
+        let target = cursor
+            .coordinates
+            .get_transform(tilemap.rules.topology, 0.0)
+            .translation
+            .truncate();
+        let margin = (half_view - Vec2::splat(TILE_SIZE)).max(Vec2::ZERO);
+        let distance = target - view_center;
+        view_center += distance - distance.clamp(-margin, margin);
+    }
+
+    if let Some(mut board) = tilemap.world_rect() {
+        // leave the ghost of the opposite edges within reach
+        if tilemap.rules.wrap {
+            board = board.inflate(GHOST_TILES as f32 * TILE_SIZE);
+        }
+        // and the layers shown beside the board
+        if tilemap.depth > 1 {
+            let beside = tilemap.layer_offset(1).x;
+            board.min.x -= beside;
+            board.max.x += beside;
+        }
+        let limit = (board.half_size() - half_view).max(Vec2::ZERO);
+        view_center = view_center.clamp(board.center() - limit, board.center() + limit);
+    }
+    transform.translation = (view_center + offset).extend(transform.translation.z);
+}
+
+/// largest offset of a full shake, in logical pixels.
+const SHAKE_PIXELS: f32 = 14.0;
+/// trauma lost per second.
+const SHAKE_DECAY: f32 = 1.5;
+
+/// shakes the view by moving the projection's origin rather than the camera,
+/// so panning and clamping never see the offset.
+pub fn update_camera_shake(
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    time: Res<Time>,
+    mut shake: ResMut<CameraShake>,
+    mut projection: Single<&mut OrthographicProjection, With<MainCamera>>,
+) {
+    if shake.trauma <= 0.0 {
+        return;
+    }
+
+    shake.trauma = (shake.trauma - SHAKE_DECAY * time.delta_secs()).max(0.0);
+
+    // squaring makes the shake die down quickly instead of fading linearly
+    let strength = shake.trauma * shake.trauma * SHAKE_PIXELS;
+    let direction = Vec2::new(rand::random::<f32>(), rand::random::<f32>()) * 2.0 - Vec2::ONE;
+    let offset = direction * strength / q_windows.single().size().max(Vec2::ONE);
+
+    projection.viewport_origin = Vec2::splat(0.5) + offset;
+}
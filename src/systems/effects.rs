@@ -0,0 +1,199 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+
+use crate::components::{MainCamera, Particle};
+use crate::events::{Effect, SpawnEffectsEvent};
+use crate::resources::{ParticlePool, TileMap};
+use crate::TILE_SIZE;
+
+/// most particles alive at once. effects spawned while all are in use are cut short.
+const MAX_PARTICLES: usize = 4096;
+/// time over which a particle fades out at the end of its lifetime, in seconds.
+const FADE_SECS: f32 = 0.4;
+const PARTICLE_Z: f32 = 2.0;
+
+pub fn spawn_particle_pool(mut commands: Commands) {
+    let free = (0..MAX_PARTICLES)
+        .map(|_| {
+            commands
+                .spawn((
+                    Particle::default(),
+                    Sprite::from_color(Color::WHITE, Vec2::splat(TILE_SIZE)),
+                    Transform::default(),
+                    Visibility::Hidden,
+                ))
+                .id()
+        })
+        .collect();
+
+    commands.insert_resource(ParticlePool { free });
+}
+
+fn random_in(min: f32, max: f32) -> f32 {
+    min + rand::random::<f32>() * (max - min)
+}
+
+/// particles of an effect with their offset from the tile center and size,
+/// relative to a tile.
+fn emit(effect: Effect) -> Vec<(Particle, Vec2, Vec2)> {
+    match effect {
+        Effect::RevealBurst => {
+            let offset = Vec2::new(random_in(-0.5, 0.5), random_in(-0.5, 0.5)) * TILE_SIZE;
+            let particle = Particle {
+                velocity: Vec2::from_angle(PI * rand::random::<f32>()) * 200.0,
+                gravity: 600.0,
+                color: Color::srgb(7.5, 7.5, 7.5),
+                lifetime: 1.2,
+                ..default()
+            };
+
+            vec![(particle, offset, Vec2::splat(random_in(0.2, 0.7)))]
+        }
+        Effect::Explosion => {
+            let ring_count = 48;
+            let shockwave = (0..ring_count).map(|index| {
+                let direction = Vec2::from_angle(TAU * index as f32 / ring_count as f32);
+                let particle = Particle {
+                    velocity: direction * 900.0,
+                    drag: 3.0,
+                    color: Color::srgb(10.0, 5.0, 1.0),
+                    lifetime: 0.7,
+                    ..default()
+                };
+
+                (particle, direction * TILE_SIZE * 0.5, Vec2::splat(0.25))
+            });
+
+            let debris = (0..24).map(|_| {
+                let particle = Particle {
+                    velocity: Vec2::from_angle(TAU * rand::random::<f32>())
+                        * random_in(150.0, 450.0),
+                    gravity: 700.0,
+                    drag: 0.5,
+                    spin: random_in(-10.0, 10.0),
+                    color: Color::srgb(random_in(4.0, 8.0), 1.0, 0.2),
+                    lifetime: random_in(1.0, 1.6),
+                    ..default()
+                };
+
+                (particle, Vec2::ZERO, Vec2::splat(random_in(0.15, 0.35)))
+            });
+
+            shockwave.chain(debris).collect()
+        }
+        Effect::Confetti => (0..60)
+            .map(|_| {
+                let angle = PI / 2.0 + random_in(-0.6, 0.6);
+                let particle = Particle {
+                    velocity: Vec2::from_angle(angle) * random_in(400.0, 900.0),
+                    gravity: 400.0,
+                    drag: 1.5,
+                    spin: random_in(-12.0, 12.0),
+                    color: Color::hsl(random_in(0.0, 360.0), 1.0, 0.6),
+                    lifetime: random_in(2.0, 3.0),
+                    ..default()
+                };
+
+                (particle, Vec2::ZERO, Vec2::new(0.3, 0.15))
+            })
+            .collect(),
+        Effect::Sparkle => {
+            let particle = Particle {
+                velocity: Vec2::new(random_in(-20.0, 20.0), random_in(60.0, 120.0)),
+                color: Color::srgb(6.0, 5.0, 1.5),
+                lifetime: random_in(0.8, 1.2),
+                ..default()
+            };
+
+            vec![(particle, Vec2::ZERO, Vec2::splat(random_in(0.15, 0.3)))]
+        }
+    }
+}
+
+pub fn handle_spawn_effects(
+    mut event_reader: EventReader<SpawnEffectsEvent>,
+    mut pool: ResMut<ParticlePool>,
+    mut particles: Query<(&mut Particle, &mut Transform, &mut Sprite, &mut Visibility)>,
+    tilemap: Res<TileMap>,
+) {
+    'events: for &SpawnEffectsEvent {
+        coordinates,
+        effect,
+    } in event_reader.read()
+    {
+        // tiles of other layers are out of view
+        if coordinates.layer != tilemap.view_layer {
+            continue;
+        }
+
+        let center = coordinates.get_transform(tilemap.rules.topology, PARTICLE_Z);
+
+        for (particle, offset, size) in emit(effect) {
+            let Some(entity) = pool.free.pop() else {
+                break 'events;
+            };
+            let Ok((mut slot, mut transform, mut sprite, mut visibility)) =
+                particles.get_mut(entity)
+            else {
+                continue;
+            };
+
+            sprite.color = particle.color;
+            *slot = particle;
+            *transform = center.with_scale(size.extend(1.0));
+            transform.translation += offset.extend(0.0);
+            *visibility = Visibility::Visible;
+        }
+    }
+
+    // effects that did not fit are dropped, not spawned a frame late
+    event_reader.clear();
+}
+
+/// moves live particles, fading them out at the end of their lifetime and
+/// returning them to the pool once expired or out of view.
+pub fn update_particles(
+    mut particles: Query<(
+        Entity,
+        &mut Particle,
+        &mut Transform,
+        &mut Sprite,
+        &mut Visibility,
+    )>,
+    camera: Single<(&GlobalTransform, &OrthographicProjection), With<MainCamera>>,
+    mut pool: ResMut<ParticlePool>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    let (camera_transform, projection) = *camera;
+    let camera_center = camera_transform.translation().truncate();
+    let view = Rect {
+        min: projection.area.min + camera_center,
+        max: projection.area.max + camera_center,
+    }
+    .inflate(TILE_SIZE);
+
+    for (entity, mut particle, mut transform, mut sprite, mut visibility) in particles.iter_mut() {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        particle.age += dt;
+        if particle.age >= particle.lifetime || !view.contains(transform.translation.truncate()) {
+            *visibility = Visibility::Hidden;
+            pool.free.push(entity);
+            continue;
+        }
+
+        transform.translation += particle.velocity.extend(0.0) * dt;
+        transform.rotate_z(particle.spin * dt);
+
+        let drag = (1.0 - particle.drag * dt).max(0.0);
+        particle.velocity = particle.velocity * drag + Vec2::Y * -particle.gravity * dt;
+
+        let fade = ((particle.lifetime - particle.age) / FADE_SECS).min(1.0);
+        sprite.color = particle.color.with_alpha(particle.color.alpha() * fade);
+    }
+}
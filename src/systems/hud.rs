@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+use crate::components::{HudLayer, HudMines, HudTimer};
+use crate::resources::{GameClock, GameState, TileMap};
+use crate::HUD_HEIGHT;
+
+pub fn spawn_hud(mut commands: Commands) {
+    let text_font = TextFont {
+        font_size: 24.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Px(HUD_HEIGHT),
+                padding: UiRect::horizontal(Val::Px(16.0)),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb_u8(20, 20, 20)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((HudMines, Text::default(), text_font.clone()));
+            parent.spawn((HudLayer, Text::default(), text_font.clone()));
+            parent.spawn((HudTimer, Text::default(), text_font));
+        });
+}
+
+pub fn tick_game_clock(time: Res<Time>, gamestate: Res<GameState>, mut clock: ResMut<GameClock>) {
+    if *gamestate == GameState::Ongoing {
+        clock.elapsed += time.delta_secs();
+    }
+}
+
+pub fn update_hud(
+    tilemap: Res<TileMap>,
+    clock: Res<GameClock>,
+    mut mines: Single<&mut Text, (With<HudMines>, Without<HudTimer>)>,
+    mut timer: Single<&mut Text, (With<HudTimer>, Without<HudMines>)>,
+) {
+    if tilemap.is_changed() {
+        mines.0 = match tilemap.is_endless() {
+            // flips stop at the first mine, so this is the score of the game
+            true => format!("Cleared: {}", tilemap.cleared_count()),
+            false => {
+                let remaining = tilemap.mine_count as i64 - tilemap.marked_count() as i64;
+                let anti_mines = tilemap.rules.anti_mines as i64;
+                match anti_mines {
+                    0 => format!("Mines: {remaining}"),
+                    _ => {
+                        let anti_remaining = anti_mines - tilemap.anti_marked_count() as i64;
+                        format!("Mines: {remaining}  Anti-mines: {anti_remaining}")
+                    }
+                }
+            }
+        };
+    }
+
+    if clock.is_changed() {
+        timer.0 = format!("Time: {}", clock.elapsed as u64);
+    }
+}
+
+/// shows the layer in view, on boards with several layers.
+pub fn update_hud_layer(tilemap: Res<TileMap>, mut layer: Single<&mut Text, With<HudLayer>>) {
+    if !tilemap.is_changed() {
+        return;
+    }
+
+    layer.0 = match tilemap.depth {
+        1 => String::new(),
+        depth => format!("Layer: {}/{depth}", tilemap.view_layer + 1),
+    };
+}
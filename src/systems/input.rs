@@ -0,0 +1,524 @@
+use bevy::input::mouse::MouseWheel;
+use bevy::window::WindowCloseRequested;
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::components::{Hover, LongPressIndicator, MainCamera, StencilMarker};
+use crate::events::{NewGameEvent, TileAction, TileActionEvent};
+use crate::resources::{
+    Action, Coordinates, Cursor, CursorSource, MeshHandles, MouseDrag, Settings, Stencil, TileMap,
+    TileMaterialHandles, Topology, TouchPress,
+};
+use crate::HUD_HEIGHT;
+
+/// world position under the mouse, if it is inside the window and not over the HUD.
+pub(super) fn cursor_world_position(
+    window: &Window,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<Vec2> {
+    let position = window
+        .cursor_position()
+        .filter(|position| position.y >= HUD_HEIGHT)?;
+    camera.viewport_to_world_2d(camera_transform, position).ok()
+}
+
+pub fn handle_click(
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut tile_actions: EventWriter<TileActionEvent>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    drag: Res<MouseDrag>,
+    keys: Res<ButtonInput<KeyCode>>,
+    tilemap: Res<TileMap>,
+) {
+    let (camera, camera_transform) = *camera;
+    let Some(mouse_pos) = cursor_world_position(q_windows.single(), camera, camera_transform)
+    else {
+        return;
+    };
+
+    let coordinates = tilemap.tile_at(mouse_pos);
+
+    // the left button reveals on release, since holding it down may start a pan
+    if buttons.just_released(MouseButton::Left) && !drag.dragging {
+        tile_actions.send(TileActionEvent {
+            action: TileAction::Reveal,
+            coordinates,
+        });
+    }
+
+    let bindings = [
+        (MouseButton::Right, TileAction::Flag),
+        (MouseButton::Middle, TileAction::Chord),
+    ];
+
+    // shift takes a flag off a stack instead of adding one
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    for (button, action) in bindings {
+        if buttons.just_pressed(button) {
+            let action = match (action, shift) {
+                (TileAction::Flag, true) => TileAction::Unflag,
+                _ => action,
+            };
+            tile_actions.send(TileActionEvent {
+                action,
+                coordinates,
+            });
+        }
+    }
+}
+
+pub fn handle_mouse_movement(
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut cursor_moved_events: EventReader<CursorMoved>,
+    mut cursor: ResMut<Cursor>,
+    tilemap: Res<TileMap>,
+) {
+    let (camera, camera_transform) = *camera;
+    let moved = cursor_moved_events.read().last().is_some();
+    let cursor_pos = cursor_world_position(q_windows.single(), camera, camera_transform);
+
+    match cursor_pos {
+        // the camera may move under a still mouse, so keep following it
+        Some(cursor_pos) if moved || cursor.source == CursorSource::Mouse => {
+            let coordinates = tilemap.tile_at(cursor_pos);
+            if moved || cursor.coordinates != coordinates {
+                *cursor = Cursor {
+                    coordinates,
+                    source: CursorSource::Mouse,
+                    visible: true,
+                };
+            }
+        }
+        // check if cursor is outside window
+        None if cursor.source == CursorSource::Mouse && cursor.visible => {
+            cursor.visible = false;
+        }
+        _ => {}
+    }
+}
+
+pub fn handle_keyboard(
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<Settings>,
+    mut cursor: ResMut<Cursor>,
+    mut tile_actions: EventWriter<TileActionEvent>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+    tilemap: Res<TileMap>,
+) {
+    let bindings = &settings.key_bindings;
+    let mut target = cursor.coordinates.with_layer(tilemap.view_layer);
+
+    if bindings.just_pressed(Action::NewGame, &keys) {
+        new_game_events.send(NewGameEvent);
+        return;
+    }
+
+    if bindings.just_pressed(Action::Up, &keys) {
+        target.row -= 1;
+    }
+    if bindings.just_pressed(Action::Down, &keys) {
+        target.row += 1;
+    }
+    if bindings.just_pressed(Action::Left, &keys) {
+        target.col -= 1;
+    }
+    if bindings.just_pressed(Action::Right, &keys) {
+        target.col += 1;
+    }
+    if bindings.just_pressed(Action::NextUnrevealed, &keys) {
+        target = tilemap.next_unrevealed(&target).unwrap_or(target);
+    }
+    if bindings.just_pressed(Action::NextFrontier, &keys) {
+        target = tilemap.next_frontier(&target).unwrap_or(target);
+    }
+
+    // the cursor steps across the seam of a board that wraps around
+    let target = tilemap.wrap_coordinates(&target);
+    if target != cursor.coordinates && tilemap.contains(&target) {
+        *cursor = Cursor {
+            coordinates: target,
+            source: CursorSource::Keyboard,
+            visible: true,
+        };
+    }
+
+    let actions = [
+        (Action::Reveal, TileAction::Reveal),
+        (Action::Flag, TileAction::Flag),
+        (Action::Unflag, TileAction::Unflag),
+        (Action::Chord, TileAction::Chord),
+    ];
+
+    for (binding, action) in actions {
+        if bindings.just_pressed(binding, &keys) {
+            cursor.source = CursorSource::Keyboard;
+            cursor.visible = true;
+            tile_actions.send(TileActionEvent {
+                action,
+                coordinates: cursor.coordinates,
+            });
+        }
+    }
+}
+
+/// how far a stick must be pushed before it moves the cursor.
+pub(super) const STICK_THRESHOLD: f32 = 0.5;
+/// delay between cursor steps while a stick is held.
+const STICK_REPEAT_SECS: f32 = 0.15;
+
+/// whether a shoulder button is held. bevy names them `LeftTrigger` and
+/// `RightTrigger`, the analog triggers are the ones ending in 2.
+pub(super) fn shoulder_pressed(gamepad: &Gamepad) -> bool {
+    gamepad.any_pressed([GamepadButton::LeftTrigger, GamepadButton::RightTrigger])
+}
+
+pub fn handle_gamepad(
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    mut stick_cooldown: Local<f32>,
+    mut cursor: ResMut<Cursor>,
+    mut tile_actions: EventWriter<TileActionEvent>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+    tilemap: Res<TileMap>,
+) {
+    *stick_cooldown -= time.delta_secs();
+
+    for gamepad in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::Select) {
+            new_game_events.send(NewGameEvent);
+            return;
+        }
+
+        let mut target = cursor.coordinates.with_layer(tilemap.view_layer);
+
+        if gamepad.just_pressed(GamepadButton::DPadUp) {
+            target.row -= 1;
+        }
+        if gamepad.just_pressed(GamepadButton::DPadDown) {
+            target.row += 1;
+        }
+        if gamepad.just_pressed(GamepadButton::DPadLeft) {
+            target.col -= 1;
+        }
+        if gamepad.just_pressed(GamepadButton::DPadRight) {
+            target.col += 1;
+        }
+
+        // the left stick pans the camera while a shoulder button is held
+        let stick = gamepad.left_stick();
+        if stick.length() < STICK_THRESHOLD || shoulder_pressed(gamepad) {
+            *stick_cooldown = 0.0;
+        } else if *stick_cooldown <= 0.0 {
+            *stick_cooldown = STICK_REPEAT_SECS;
+
+            if stick.x.abs() >= STICK_THRESHOLD {
+                target.col += stick.x.signum() as i64;
+            }
+            if stick.y.abs() >= STICK_THRESHOLD {
+                target.row -= stick.y.signum() as i64;
+            }
+        }
+
+        let target = tilemap.wrap_coordinates(&target);
+        if target != cursor.coordinates && tilemap.contains(&target) {
+            *cursor = Cursor {
+                coordinates: target,
+                source: CursorSource::Gamepad,
+                visible: true,
+            };
+        }
+
+        let actions = [
+            (GamepadButton::South, TileAction::Reveal),
+            (GamepadButton::East, TileAction::Flag),
+            (GamepadButton::North, TileAction::Unflag),
+            (GamepadButton::West, TileAction::Chord),
+        ];
+
+        for (button, action) in actions {
+            if gamepad.just_pressed(button) {
+                cursor.source = CursorSource::Gamepad;
+                cursor.visible = true;
+                tile_actions.send(TileActionEvent {
+                    action,
+                    coordinates: cursor.coordinates,
+                });
+            }
+        }
+    }
+}
+
+/// moves the view through the layers of a layered board, taking the cursor along.
+pub fn handle_layer_scroll(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut wheel_events: EventReader<MouseWheel>,
+    gamepads: Query<&Gamepad>,
+    settings: Res<Settings>,
+    mut cursor: ResMut<Cursor>,
+    mut tilemap: ResMut<TileMap>,
+) {
+    let bindings = &settings.key_bindings;
+    let mut step = 0;
+
+    if bindings.just_pressed(Action::PreviousLayer, &keys) {
+        step -= 1;
+    }
+    if bindings.just_pressed(Action::NextLayer, &keys) {
+        step += 1;
+    }
+
+    // shift turns the mouse wheel from zooming to scrolling through layers,
+    // a notch at a time however fine the scroll
+    let wheel: f32 = wheel_events.read().map(|event| event.y).sum();
+    if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) && wheel != 0.0 {
+        step -= wheel.signum() as i64;
+    }
+
+    for gamepad in gamepads.iter() {
+        if gamepad.just_pressed(GamepadButton::LeftTrigger2) {
+            step -= 1;
+        }
+        if gamepad.just_pressed(GamepadButton::RightTrigger2) {
+            step += 1;
+        }
+    }
+
+    let layer = (tilemap.view_layer + step).clamp(0, tilemap.depth - 1);
+    if layer == tilemap.view_layer {
+        return;
+    }
+
+    tilemap.view_layer = layer;
+    cursor.coordinates.layer = layer;
+}
+
+/// how far a finger may drift, in pixels, before a press stops counting as a tap.
+const TOUCH_SLOP: f32 = 12.0;
+
+pub fn handle_touch(
+    touches: Res<Touches>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut press: ResMut<TouchPress>,
+    mut cursor: ResMut<Cursor>,
+    mut tile_actions: EventWriter<TileActionEvent>,
+    tilemap: Res<TileMap>,
+) {
+    // a second finger turns the gesture into a pinch or pan
+    if touches.iter().count() > 1 {
+        if press.touch.is_some() {
+            *press = TouchPress::default();
+        }
+        return;
+    }
+
+    let (camera, camera_transform) = *camera;
+    for touch in touches.iter_just_pressed() {
+        let Ok(position) = camera.viewport_to_world_2d(camera_transform, touch.position()) else {
+            continue;
+        };
+
+        let coordinates = tilemap.tile_at(position);
+        *press = TouchPress {
+            touch: Some(touch.id()),
+            coordinates,
+            started: time.elapsed_secs(),
+            progress: 0.0,
+            flagged: false,
+        };
+        *cursor = Cursor {
+            coordinates,
+            source: CursorSource::Touch,
+            visible: true,
+        };
+    }
+
+    let Some(id) = press.touch else {
+        return;
+    };
+
+    let Some(touch) = touches.get_pressed(id) else {
+        if !press.flagged && touches.just_released(id) {
+            let coordinates = press.coordinates;
+            let action = match tilemap.contains(&coordinates)
+                && tilemap[coordinates].is_flipped()
+                && tilemap.can_reveal_neighbors(coordinates)
+            {
+                true => TileAction::Chord,
+                false => TileAction::Reveal,
+            };
+
+            tile_actions.send(TileActionEvent {
+                action,
+                coordinates,
+            });
+        }
+
+        *press = TouchPress::default();
+        return;
+    };
+
+    if touch.distance().length() > TOUCH_SLOP {
+        *press = TouchPress::default();
+        return;
+    }
+
+    if press.flagged {
+        return;
+    }
+
+    press.progress = ((time.elapsed_secs() - press.started) / settings.long_press_secs).min(1.0);
+    if press.progress >= 1.0 {
+        press.flagged = true;
+        tile_actions.send(TileActionEvent {
+            action: TileAction::Flag,
+            coordinates: press.coordinates,
+        });
+    }
+}
+
+pub fn update_hover(
+    mut query_hover: Query<
+        (
+            &mut Transform,
+            &mut Visibility,
+            &mut MeshMaterial2d<ColorMaterial>,
+        ),
+        With<Hover>,
+    >,
+    cursor: Res<Cursor>,
+    tilemap: Res<TileMap>,
+    material_handles: Res<TileMaterialHandles>,
+) {
+    let (mut transform, mut visibility, mut material) = query_hover.single_mut();
+
+    if !cursor.visible || !tilemap.contains(&cursor.coordinates) {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+
+    // the keyboard cursor stays visible over revealed tiles so it can chord,
+    // but is dimmed so the number underneath stays readable
+    let hoverable = tilemap[cursor.coordinates].is_hoverable();
+    material.0 = match (hoverable, cursor.source) {
+        (true, _) => material_handles.hover.clone(),
+        (false, CursorSource::Keyboard | CursorSource::Gamepad) => material_handles.cursor.clone(),
+        (false, CursorSource::Mouse | CursorSource::Touch) => {
+            *visibility = Visibility::Hidden;
+            return;
+        }
+    };
+
+    *transform = cursor
+        .coordinates
+        .get_transform(tilemap.rules.topology, 1.0);
+    *visibility = Visibility::Visible;
+}
+
+/// size of a stencil dot relative to a tile.
+const STENCIL_MARKER_SCALE: f32 = 0.3;
+
+/// marks the tiles counted by the tile under the cursor, so unusual
+/// stencils can be read off the board. markers are spawned as needed.
+pub fn update_stencil_overlay(
+    mut commands: Commands,
+    mut markers: Query<(&mut Transform, &mut Visibility), With<StencilMarker>>,
+    cursor: Res<Cursor>,
+    tilemap: Res<TileMap>,
+    mesh_handles: Res<MeshHandles>,
+    material_handles: Res<TileMaterialHandles>,
+) {
+    let shown = cursor.visible
+        && tilemap.contains(&cursor.coordinates)
+        && tilemap.rules.topology == Topology::Square
+        && tilemap.rules.stencil != Stencil::Standard;
+    // tiles counted on other layers are out of view
+    let neighbors: Vec<Coordinates> = match shown {
+        true => tilemap
+            .get_neighbors(&cursor.coordinates)
+            .into_iter()
+            .filter(|neighbor| neighbor.layer == cursor.coordinates.layer)
+            .collect(),
+        false => Vec::new(),
+    };
+
+    let mut markers = markers.iter_mut();
+    for neighbor in &neighbors {
+        let transform = neighbor
+            .get_transform(tilemap.rules.topology, 1.1)
+            .with_scale(Vec3::splat(STENCIL_MARKER_SCALE));
+
+        match markers.next() {
+            Some((mut marker, mut visibility)) => {
+                *marker = transform;
+                *visibility = Visibility::Visible;
+            }
+            None => {
+                commands.spawn((
+                    StencilMarker,
+                    Mesh2d(mesh_handles.tile_mesh.clone()),
+                    MeshMaterial2d(material_handles.stencil.clone()),
+                    transform,
+                ));
+            }
+        }
+    }
+
+    for (_, mut visibility) in markers {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+pub fn update_long_press_indicator(
+    press: Res<TouchPress>,
+    indicator: Single<(&mut Transform, &mut Visibility), With<LongPressIndicator>>,
+    tilemap: Res<TileMap>,
+) {
+    let (mut transform, mut visibility) = indicator.into_inner();
+
+    if press.touch.is_none() || press.flagged || press.progress <= 0.0 {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+
+    *transform = press.coordinates.get_transform(tilemap.rules.topology, 1.2);
+    transform.scale = Vec3::splat(press.progress);
+    *visibility = Visibility::Visible;
+}
+
+pub fn handle_settings_hotkeys(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    if settings
+        .key_bindings
+        .just_pressed(Action::CyclePalette, &keys)
+    {
+        settings.palette = settings.palette.next();
+    }
+
+    if settings
+        .key_bindings
+        .just_pressed(Action::ToggleShapeMarkers, &keys)
+    {
+        settings.shape_markers = !settings.shape_markers;
+    }
+
+    if settings.key_bindings.just_pressed(Action::Mute, &keys) {
+        settings.muted = !settings.muted;
+    }
+
+    if settings.key_bindings.just_pressed(Action::Autoplay, &keys) {
+        settings.autoplay = !settings.autoplay;
+    }
+}
+
+pub fn handle_window_close(
+    mut window_close_events: EventReader<WindowCloseRequested>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if let Some(_event) = window_close_events.read().next() {
+        exit.send(AppExit::Success);
+    }
+}
@@ -0,0 +1,90 @@
+use bevy::core_pipeline::bloom::Bloom;
+use bevy::prelude::*;
+
+use crate::events::{Effect, MineHitEvent, SpawnEffectsEvent, TileChange, TileChangedEvent};
+use crate::resources::{CameraShake, Coordinates, GameState, LossSequence, TileMap};
+use crate::BLOOM_INTENSITY;
+
+/// delay between the explosion and the first mine of the ripple, in seconds.
+const RIPPLE_DELAY_SECS: f32 = 0.5;
+/// time the ripple takes to reach the farthest mine, in seconds.
+const RIPPLE_SECS: f32 = 1.5;
+/// bloom intensity added at the peak of the flash.
+const FLASH_INTENSITY: f32 = 0.8;
+/// how fast the flash fades, per second.
+const FLASH_DECAY: f32 = 6.0;
+
+/// starts the loss sequence: a shake, then every hidden mine revealed in a
+/// ripple spreading out from the one that was hit.
+pub fn handle_mine_hit(
+    mut mine_hit_events: EventReader<MineHitEvent>,
+    mut sequence: ResMut<LossSequence>,
+    mut shake: ResMut<CameraShake>,
+    tilemap: Res<TileMap>,
+) {
+    let Some(&MineHitEvent {
+        coordinates: origin,
+    }) = mine_hit_events.read().last()
+    else {
+        return;
+    };
+
+    // flagged mines stay flagged
+    let mut ripple: Vec<(f32, Coordinates)> = tilemap
+        .iter()
+        .filter(|(_, tile)| tile.contains_mine() && tile.is_hoverable())
+        .map(|(coordinates, _)| (origin.distance(&coordinates), coordinates))
+        .collect();
+
+    let farthest = ripple
+        .iter()
+        .map(|&(distance, _)| distance)
+        .fold(1.0, f32::max);
+    for (time, _) in ripple.iter_mut() {
+        *time = RIPPLE_DELAY_SECS + RIPPLE_SECS * *time / farthest;
+    }
+    ripple.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    *sequence = LossSequence {
+        elapsed: 0.0,
+        ripple,
+    };
+    shake.trauma = 1.0;
+}
+
+pub fn update_loss_sequence(
+    time: Res<Time>,
+    gamestate: Res<GameState>,
+    mut sequence: ResMut<LossSequence>,
+    mut bloom: Single<&mut Bloom>,
+    mut effects: EventWriter<SpawnEffectsEvent>,
+    mut tile_changed_events: EventWriter<TileChangedEvent>,
+    mut tilemap: ResMut<TileMap>,
+) {
+    if *gamestate != GameState::Lost {
+        return;
+    }
+
+    sequence.elapsed += time.delta_secs();
+    bloom.intensity = BLOOM_INTENSITY + FLASH_INTENSITY * (-FLASH_DECAY * sequence.elapsed).exp();
+
+    while let Some(&(reveal_at, coordinates)) = sequence.ripple.last() {
+        if reveal_at > sequence.elapsed {
+            break;
+        }
+
+        sequence.ripple.pop();
+        if tilemap[coordinates].flip() {
+            tile_changed_events.send(TileChangedEvent {
+                coordinates,
+                change: TileChange::Revealed {
+                    origin: coordinates,
+                },
+            });
+            effects.send(SpawnEffectsEvent {
+                coordinates,
+                effect: Effect::RevealBurst,
+            });
+        }
+    }
+}
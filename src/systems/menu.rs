@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+
+use crate::components::{MenuEntry, MenuError, MenuRoot};
+use crate::events::NewGameEvent;
+use crate::resources::{Action, Menu, MenuItem, Settings};
+
+pub fn menu_closed(menu: Res<Menu>) -> bool {
+    !menu.open
+}
+
+pub fn spawn_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            MenuRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                // entries that do not fit the window height wrap into more columns
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                align_content: AlignContent::Center,
+                row_gap: Val::Px(8.0),
+                column_gap: Val::Px(24.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            GlobalZIndex(1),
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            for index in 0..MenuItem::ALL.len() {
+                parent.spawn((
+                    MenuEntry(index),
+                    Button,
+                    Text::default(),
+                    TextFont {
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    Node {
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::NONE),
+                ));
+            }
+
+            parent.spawn((
+                MenuError,
+                Text::default(),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.4, 0.4)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(24.0),
+                    ..default()
+                },
+            ));
+        });
+}
+
+pub fn handle_menu_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    entries: Query<(&Interaction, &MenuEntry), Changed<Interaction>>,
+    mut menu: ResMut<Menu>,
+    mut settings: ResMut<Settings>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+) {
+    let bindings = &settings.key_bindings;
+    let gamepad_pressed =
+        |button: GamepadButton| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+
+    if bindings.just_pressed(Action::Menu, &keys) || gamepad_pressed(GamepadButton::Start) {
+        match menu.open {
+            true => menu.close(),
+            false => menu.open = true,
+        }
+        return;
+    }
+
+    if !menu.open {
+        return;
+    }
+
+    if gamepad_pressed(GamepadButton::East) {
+        menu.close();
+        return;
+    }
+
+    let up = bindings.just_pressed(Action::Up, &keys) || gamepad_pressed(GamepadButton::DPadUp);
+    let down =
+        bindings.just_pressed(Action::Down, &keys) || gamepad_pressed(GamepadButton::DPadDown);
+    let left =
+        bindings.just_pressed(Action::Left, &keys) || gamepad_pressed(GamepadButton::DPadLeft);
+    let right =
+        bindings.just_pressed(Action::Right, &keys) || gamepad_pressed(GamepadButton::DPadRight);
+    let mut confirm =
+        bindings.just_pressed(Action::Reveal, &keys) || gamepad_pressed(GamepadButton::South);
+
+    // the menu is only written to when the selection really moves, so the
+    // click sound and the redraw in `update_menu` follow actual changes
+    for (interaction, entry) in entries.iter() {
+        if *interaction == Interaction::None {
+            continue;
+        }
+        if menu.selected != entry.0 {
+            menu.selected = entry.0;
+        }
+        confirm |= *interaction == Interaction::Pressed;
+    }
+
+    let moved = down as i32 - up as i32;
+    if moved != 0 {
+        menu.move_selection(moved);
+    }
+
+    let step = match (confirm, left, right) {
+        (true, _, _) => 0,
+        (false, true, false) => -1,
+        (false, false, true) => 1,
+        _ => return,
+    };
+
+    let item = menu.selected_item();
+    if item.activate(&mut settings, step) {
+        menu.close();
+
+        if item == MenuItem::NewGame {
+            new_game_events.send(NewGameEvent);
+        }
+    }
+}
+
+pub fn update_menu(
+    menu: Res<Menu>,
+    settings: Res<Settings>,
+    mut root: Single<&mut Visibility, With<MenuRoot>>,
+    mut entries: Query<(&MenuEntry, &mut Text, &mut BackgroundColor)>,
+    mut error: Single<&mut Text, (With<MenuError>, Without<MenuEntry>)>,
+) {
+    if !menu.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    **root = match menu.open {
+        true => Visibility::Visible,
+        false => Visibility::Hidden,
+    };
+
+    error.0 = menu.error.clone().unwrap_or_default();
+
+    for (entry, mut text, mut background) in entries.iter_mut() {
+        text.0 = MenuItem::ALL[entry.0].label(&settings);
+        background.0 = match entry.0 == menu.selected {
+            true => Color::srgba(1.0, 1.0, 1.0, 0.2),
+            false => Color::NONE,
+        };
+    }
+}
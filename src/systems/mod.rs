@@ -0,0 +1,27 @@
+mod animation;
+mod audio;
+mod autoplay;
+mod board;
+mod camera;
+mod effects;
+mod hud;
+mod input;
+mod loss;
+mod menu;
+mod render;
+mod setup;
+mod win;
+
+pub use animation::*;
+pub use audio::*;
+pub use autoplay::*;
+pub use board::*;
+pub use camera::*;
+pub use effects::*;
+pub use hud::*;
+pub use input::*;
+pub use loss::*;
+pub use menu::*;
+pub use render::*;
+pub use setup::*;
+pub use win::*;
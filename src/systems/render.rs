@@ -0,0 +1,717 @@
+use std::collections::HashSet;
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+use bevy::render::view::RenderLayers;
+use bevy::sprite::AlphaMode2d;
+
+use crate::components::{Chunk, Ghost, LayerGhost, MainCamera};
+use crate::events::{NewGameEvent, TileChangedEvent};
+use crate::resources::{
+    Coordinates, GameState, Glyph, GlyphAtlas, MeshHandles, Settings, Tile, TileAnimations,
+    TileMap, TileMaterialHandles, Topology, ATLAS_CELL_SIZE, ATLAS_COLUMNS,
+};
+use crate::{CHUNK_SIZE, FONT_SIZE, GHOST_TILES, TILE_SIZE};
+
+use super::FontHandle;
+
+/// render layer only seen by the camera drawing the glyph atlas.
+const ATLAS_LAYER: usize = 1;
+
+/// draws every glyph once into an offscreen texture, white on transparent,
+/// so chunk meshes can tint them with vertex colors.
+pub fn setup_glyph_atlas(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    font: Res<FontHandle>,
+) {
+    let cell = ATLAS_CELL_SIZE as f32;
+    let size = Extent3d {
+        width: ATLAS_CELL_SIZE * ATLAS_COLUMNS,
+        height: ATLAS_CELL_SIZE,
+        depth_or_array_layers: 1,
+    };
+
+    let mut image = Image::new_fill(
+        size,
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Bgra8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    let image = images.add(image);
+
+    let layer = RenderLayers::layer(ATLAS_LAYER);
+    let atlas_center = Vec3::new(size.width as f32 / 2.0, -cell / 2.0, 0.0);
+
+    commands.spawn((
+        Camera2d,
+        Camera {
+            target: RenderTarget::Image(image.clone()),
+            order: -1,
+            clear_color: ClearColorConfig::Custom(Color::NONE),
+            ..default()
+        },
+        Transform::from_translation(atlas_center),
+        layer.clone(),
+    ));
+
+    let cell_center = |glyph: Glyph| {
+        let x = (glyph.index() as f32 + 0.5) * cell;
+        Transform::from_xyz(x, -cell / 2.0, 0.0)
+    };
+
+    commands.spawn((
+        Sprite::from_color(Color::WHITE, Vec2::splat(cell)),
+        cell_center(Glyph::Solid),
+        layer.clone(),
+    ));
+
+    for digit in 0..=9 {
+        commands.spawn((
+            Text2d(digit.to_string()),
+            TextFont {
+                font: font.0.clone(),
+                font_size: FONT_SIZE / TILE_SIZE * cell,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            cell_center(Glyph::Digit(digit)),
+            layer.clone(),
+        ));
+    }
+
+    let white = materials.add(Color::WHITE);
+    let flag = meshes.add(Triangle2d::new(
+        Vec2::new(-cell * 0.25, cell * 0.3),
+        Vec2::new(-cell * 0.25, -cell * 0.3),
+        Vec2::new(cell * 0.3, 0.0),
+    ));
+    let mine = meshes.add(Circle::new(cell * 0.3));
+    let hexagon = meshes.add(RegularPolygon::new(cell / 2.0, 6));
+    let minus = meshes.add(Rectangle::new(cell * 0.3, cell * 0.07));
+    let anti_mine = meshes.add(Annulus::new(cell * 0.18, cell * 0.3));
+    let anti_flag = meshes.add(Triangle2d::new(
+        Vec2::new(cell * 0.25, cell * 0.3),
+        Vec2::new(-cell * 0.3, 0.0),
+        Vec2::new(cell * 0.25, -cell * 0.3),
+    ));
+
+    for (glyph, mesh) in [
+        (Glyph::Flag, flag),
+        (Glyph::Mine, mine),
+        (Glyph::Hexagon, hexagon),
+        (Glyph::Minus, minus),
+        (Glyph::AntiMine, anti_mine),
+        (Glyph::AntiFlag, anti_flag),
+    ] {
+        commands.spawn((
+            Mesh2d(mesh),
+            MeshMaterial2d(white.clone()),
+            cell_center(glyph),
+            layer.clone(),
+        ));
+    }
+
+    let bar = meshes.add(Rectangle::new(cell * 0.8, cell * 0.12));
+    for angle in [FRAC_PI_4, -FRAC_PI_4] {
+        commands.spawn((
+            Mesh2d(bar.clone()),
+            MeshMaterial2d(white.clone()),
+            cell_center(Glyph::Cross).with_rotation(Quat::from_rotation_z(angle)),
+            layer.clone(),
+        ));
+    }
+
+    let material = materials.add(ColorMaterial {
+        color: Color::WHITE,
+        alpha_mode: AlphaMode2d::Blend,
+        texture: Some(image.clone()),
+    });
+
+    commands.insert_resource(GlyphAtlas { image, material });
+}
+
+/// chunks beyond the edge of the view that are meshed ahead of time,
+/// so panning does not reveal missing tiles.
+const CHUNK_MARGIN: i32 = 1;
+
+/// spawns a mesh for every chunk in view that does not have one yet,
+/// generating its tiles first on an endless board.
+pub fn load_visible_chunks(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    camera: Single<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    chunks: Query<&Chunk>,
+    mut tilemap: ResMut<TileMap>,
+    atlas: Res<GlyphAtlas>,
+    settings: Res<Settings>,
+    gamestate: Res<GameState>,
+    animations: Res<TileAnimations>,
+    time: Res<Time>,
+) {
+    let (transform, projection) = *camera;
+    let center = transform.translation.truncate();
+    let top_left = Vec2::new(projection.area.min.x, projection.area.max.y) + center;
+    let bottom_right = Vec2::new(projection.area.max.x, projection.area.min.y) + center;
+
+    let topology = tilemap.rules.topology;
+    let first = Coordinates::from_world_position(top_left, topology).chunk() - CHUNK_MARGIN;
+    let last = Coordinates::from_world_position(bottom_right, topology).chunk() + CHUNK_MARGIN;
+
+    let loaded: HashSet<IVec2> = chunks.iter().map(|chunk| chunk.0).collect();
+
+    for y in first.y..=last.y {
+        for x in first.x..=last.x {
+            let chunk = IVec2::new(x, y);
+            if loaded.contains(&chunk) || !tilemap.chunk_in_bounds(chunk) {
+                continue;
+            }
+
+            tilemap.generate_chunk(chunk);
+            let mesh = meshes.add(build_chunk_mesh(
+                &tilemap,
+                chunk,
+                &settings,
+                &gamestate,
+                &animations,
+                time.elapsed_secs(),
+            ));
+
+            commands.spawn((
+                Chunk(chunk),
+                Mesh2d(mesh),
+                MeshMaterial2d(atlas.material.clone()),
+                Transform::from_translation(chunk_origin(chunk, topology).extend(0.0)),
+            ));
+        }
+    }
+}
+
+pub fn rebuild_chunks(
+    mut tile_changed_events: EventReader<TileChangedEvent>,
+    mut shown_layer: Local<i64>,
+    chunks: Query<(&Chunk, &Mesh2d)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    tilemap: Res<TileMap>,
+    settings: Res<Settings>,
+    gamestate: Res<GameState>,
+    animations: Res<TileAnimations>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+    let layer = tilemap.view_layer;
+
+    // animations still waiting for their start look the same every frame
+    let animating = animations
+        .tiles
+        .iter()
+        .filter(|(coordinates, animation)| animation.start <= now && coordinates.layer == layer)
+        .map(|(coordinates, _)| coordinates.chunk());
+
+    let dirty: HashSet<IVec2> = tile_changed_events
+        .read()
+        .filter(|event| event.coordinates.layer == layer)
+        .map(|event| event.coordinates.chunk())
+        .chain(animating)
+        .collect();
+
+    // palette and marker changes affect every tile, and so does losing,
+    // which crosses out wrong flags. every chunk shows another layer when
+    // the view moves through the board.
+    let layer_changed = *shown_layer != layer;
+    *shown_layer = layer;
+    let rebuild_all = settings.is_changed() || gamestate.is_changed() || layer_changed;
+    if dirty.is_empty() && !rebuild_all {
+        return;
+    }
+
+    for (chunk, mesh) in chunks.iter() {
+        if !rebuild_all && !dirty.contains(&chunk.0) {
+            continue;
+        }
+
+        if let Some(mesh) = meshes.get_mut(&mesh.0) {
+            *mesh = build_chunk_mesh(&tilemap, chunk.0, &settings, &gamestate, &animations, now);
+        }
+    }
+}
+
+pub fn apply_settings(
+    settings: Res<Settings>,
+    material_handles: Res<TileMaterialHandles>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    material_handles.apply_palette(settings.palette, &mut materials);
+}
+
+/// keeps the ghost of the opposite edges around a board that wraps around in
+/// sync with the tiles it mirrors.
+pub fn rebuild_ghost(
+    mut commands: Commands,
+    mut tile_changed_events: EventReader<TileChangedEvent>,
+    mut shown_layer: Local<i64>,
+    ghost: Option<Single<(Entity, &Mesh2d), With<Ghost>>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    tilemap: Res<TileMap>,
+    atlas: Res<GlyphAtlas>,
+    settings: Res<Settings>,
+    gamestate: Res<GameState>,
+    animations: Res<TileAnimations>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+
+    let layer = tilemap.view_layer;
+
+    // only tiles along the edges of the shown layer show up in the ghost
+    let near_edge = |coordinates: &Coordinates| {
+        coordinates.layer == layer
+            && (coordinates.col < GHOST_TILES
+                || coordinates.col >= tilemap.width - GHOST_TILES
+                || coordinates.row < GHOST_TILES
+                || coordinates.row >= tilemap.height - GHOST_TILES)
+    };
+    let changed = tile_changed_events
+        .read()
+        .filter(|event| near_edge(&event.coordinates))
+        .count()
+        > 0;
+    let animating = animations
+        .tiles
+        .iter()
+        .any(|(coordinates, animation)| animation.start <= now && near_edge(coordinates));
+
+    // a new game resets the game state, and may turn wrapping on or off
+    let layer_changed = *shown_layer != layer;
+    *shown_layer = layer;
+    let rebuild_all = settings.is_changed() || gamestate.is_changed() || layer_changed;
+    if !changed && !animating && !rebuild_all {
+        return;
+    }
+
+    if !tilemap.rules.wrap || tilemap.is_endless() {
+        if let Some(ghost) = ghost {
+            commands.entity(ghost.0).despawn();
+        }
+        return;
+    }
+
+    let mesh = build_ghost_mesh(&tilemap, &settings, &gamestate, &animations, now);
+    match ghost {
+        Some(ghost) => {
+            if let Some(ghost_mesh) = meshes.get_mut(&ghost.1 .0) {
+                *ghost_mesh = mesh;
+            }
+        }
+        None => {
+            commands.spawn((
+                Ghost,
+                Mesh2d(meshes.add(mesh)),
+                MeshMaterial2d(atlas.material.clone()),
+                Transform::default(),
+            ));
+        }
+    }
+}
+
+/// keeps faded copies of the layers above and below the shown one beside
+/// the board, on boards with several layers.
+pub fn rebuild_layer_ghosts(
+    mut commands: Commands,
+    mut tile_changed_events: EventReader<TileChangedEvent>,
+    mut shown_layer: Local<i64>,
+    mut ghosts: Query<(Entity, &LayerGhost, &Mesh2d, &mut Transform)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    tilemap: Res<TileMap>,
+    atlas: Res<GlyphAtlas>,
+    settings: Res<Settings>,
+    gamestate: Res<GameState>,
+    animations: Res<TileAnimations>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+    let layer = tilemap.view_layer;
+
+    let nearby = |coordinates: &Coordinates| coordinates.layer.abs_diff(layer) == 1;
+    let changed = tile_changed_events
+        .read()
+        .filter(|event| nearby(&event.coordinates))
+        .count()
+        > 0;
+    let animating = animations
+        .tiles
+        .iter()
+        .any(|(coordinates, animation)| animation.start <= now && nearby(coordinates));
+
+    let layer_changed = *shown_layer != layer;
+    *shown_layer = layer;
+    let rebuild_all = settings.is_changed() || gamestate.is_changed() || layer_changed;
+    if !changed && !animating && !rebuild_all {
+        return;
+    }
+
+    for offset in [-1, 1] {
+        let ghost = ghosts.iter_mut().find(|(_, ghost, _, _)| ghost.0 == offset);
+        let shown = (0..tilemap.depth).contains(&(layer + offset));
+
+        match (ghost, shown) {
+            (Some((entity, _, _, _)), false) => commands.entity(entity).despawn(),
+            (Some((_, _, mesh, mut transform)), true) => {
+                // a new game may change the size of the board
+                transform.translation = tilemap.layer_offset(offset).extend(0.0);
+                if let Some(ghost_mesh) = meshes.get_mut(&mesh.0) {
+                    *ghost_mesh =
+                        build_layer_mesh(&tilemap, offset, &settings, &gamestate, &animations, now);
+                }
+            }
+            (None, true) => {
+                let mesh =
+                    build_layer_mesh(&tilemap, offset, &settings, &gamestate, &animations, now);
+                commands.spawn((
+                    LayerGhost(offset),
+                    Mesh2d(meshes.add(mesh)),
+                    MeshMaterial2d(atlas.material.clone()),
+                    Transform::from_translation(tilemap.layer_offset(offset).extend(0.0)),
+                ));
+            }
+            (None, false) => {}
+        }
+    }
+}
+
+/// swaps the shape of the hover and long press meshes when a new game
+/// changes the tile shape.
+pub fn update_tile_mesh(
+    mut new_game_events: EventReader<NewGameEvent>,
+    tilemap: Res<TileMap>,
+    mesh_handles: Res<MeshHandles>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    if new_game_events.read().count() == 0 {
+        return;
+    }
+
+    meshes.insert(&mesh_handles.tile_mesh, tilemap.rules.topology.tile_mesh());
+}
+
+/// world position of the center of the top left tile of a chunk.
+fn chunk_origin(chunk: IVec2, topology: Topology) -> Vec2 {
+    let tile = Coordinates::new(chunk.x as i64 * CHUNK_SIZE, chunk.y as i64 * CHUNK_SIZE);
+    topology.tile_center(&tile)
+}
+
+/// width of a digit relative to a tile, matching the font's advance.
+const DIGIT_WIDTH: f32 = 0.6;
+
+#[derive(Default)]
+struct ChunkMeshBuilder {
+    positions: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl ChunkMeshBuilder {
+    fn push_quad(&mut self, rect: Rect, uv: Rect, color: Color) {
+        let start = self.positions.len() as u32;
+        let color = color.to_linear().to_f32_array();
+
+        // world y grows upwards while texture v grows downwards
+        self.positions.extend([
+            [rect.min.x, rect.min.y, 0.0],
+            [rect.max.x, rect.min.y, 0.0],
+            [rect.max.x, rect.max.y, 0.0],
+            [rect.min.x, rect.max.y, 0.0],
+        ]);
+        self.uvs.extend([
+            [uv.min.x, uv.max.y],
+            [uv.max.x, uv.max.y],
+            [uv.max.x, uv.min.y],
+            [uv.min.x, uv.min.y],
+        ]);
+        self.colors.extend([color; 4]);
+        self.indices
+            .extend([start, start + 1, start + 2, start, start + 2, start + 3]);
+    }
+
+    fn push_glyph(&mut self, rect: Rect, glyph: Glyph, color: Color) {
+        self.push_quad(rect, glyph.uv_rect(), color);
+    }
+
+    /// lays the digits of `number` side by side, shrinking them to fit the tile.
+    fn push_number(&mut self, rect: Rect, number: i32, color: Color) {
+        let digits: Vec<Glyph> = number
+            .to_string()
+            .bytes()
+            .map(|byte| match byte {
+                b'-' => Glyph::Minus,
+                digit => Glyph::Digit(digit - b'0'),
+            })
+            .collect();
+
+        let natural_width = rect.width() * DIGIT_WIDTH * digits.len() as f32;
+        let scale = (rect.width() * 0.9 / natural_width).min(1.0);
+        let digit_size = Vec2::new(rect.width() * DIGIT_WIDTH, rect.height()) * scale;
+        let left = rect.center().x - digit_size.x * digits.len() as f32 / 2.0;
+
+        for (index, glyph) in digits.into_iter().enumerate() {
+            let min = Vec2::new(
+                left + digit_size.x * index as f32,
+                rect.center().y - digit_size.y / 2.0,
+            );
+            let quad = Rect::from_corners(min, min + digit_size);
+
+            // crop the square atlas cell to the digit's advance
+            let uv = glyph.uv_rect();
+            let crop = uv.width() * (1.0 - DIGIT_WIDTH) / 2.0;
+            let uv = Rect::new(uv.min.x + crop, uv.min.y, uv.max.x - crop, uv.max.y);
+
+            self.push_quad(quad, uv, color);
+        }
+    }
+
+    fn append(&mut self, other: Self) {
+        let offset = self.positions.len() as u32;
+        self.positions.extend(other.positions);
+        self.uvs.extend(other.uvs);
+        self.colors.extend(other.colors);
+        self.indices
+            .extend(other.indices.into_iter().map(|index| index + offset));
+    }
+
+    fn build(self) -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, self.colors)
+        .with_inserted_indices(Indices::U32(self.indices))
+    }
+}
+
+/// builds one quad per tile of the chunk, followed by the numbers and markers
+/// drawn over them, in coordinates relative to the center of the chunk's top
+/// left tile.
+fn build_chunk_mesh(
+    tilemap: &TileMap,
+    chunk: IVec2,
+    settings: &Settings,
+    gamestate: &GameState,
+    animations: &TileAnimations,
+    now: f32,
+) -> Mesh {
+    let origin = chunk_origin(chunk, tilemap.rules.topology);
+    let mut fills = ChunkMeshBuilder::default();
+    let mut glyphs = ChunkMeshBuilder::default();
+
+    for local_row in 0..CHUNK_SIZE {
+        for local_col in 0..CHUNK_SIZE {
+            let coordinates = Coordinates::new(
+                chunk.x as i64 * CHUNK_SIZE + local_col,
+                chunk.y as i64 * CHUNK_SIZE + local_row,
+            )
+            .with_layer(tilemap.view_layer);
+
+            if !tilemap.contains(&coordinates) {
+                continue;
+            }
+
+            let center = tilemap.rules.topology.tile_center(&coordinates) - origin;
+            push_tile(
+                &mut fills,
+                &mut glyphs,
+                tilemap,
+                coordinates,
+                center,
+                settings,
+                gamestate,
+                animations,
+                now,
+                1.0,
+            );
+        }
+    }
+
+    // glyphs come last so they are drawn over the tile fills
+    fills.append(glyphs);
+    fills.build()
+}
+
+/// transparency of the ghost tiles around a board that wraps around.
+const GHOST_ALPHA: f32 = 0.3;
+
+/// builds the faded band of `GHOST_TILES` tiles around a board that wraps
+/// around, each showing the tile on the opposite edge, in world coordinates.
+fn build_ghost_mesh(
+    tilemap: &TileMap,
+    settings: &Settings,
+    gamestate: &GameState,
+    animations: &TileAnimations,
+    now: f32,
+) -> Mesh {
+    let mut fills = ChunkMeshBuilder::default();
+    let mut glyphs = ChunkMeshBuilder::default();
+
+    for row in -GHOST_TILES..tilemap.height + GHOST_TILES {
+        for col in -GHOST_TILES..tilemap.width + GHOST_TILES {
+            let position = Coordinates::new(col, row).with_layer(tilemap.view_layer);
+            let wrapped = tilemap.wrap_coordinates(&position);
+            if tilemap.in_bounds(&position) || !tilemap.contains(&wrapped) {
+                continue;
+            }
+
+            push_tile(
+                &mut fills,
+                &mut glyphs,
+                tilemap,
+                wrapped,
+                tilemap.rules.topology.tile_center(&position),
+                settings,
+                gamestate,
+                animations,
+                now,
+                GHOST_ALPHA,
+            );
+        }
+    }
+
+    fills.append(glyphs);
+    fills.build()
+}
+
+/// builds a faded copy of the layer `offset` layers away from the shown one,
+/// in coordinates relative to the board.
+fn build_layer_mesh(
+    tilemap: &TileMap,
+    offset: i64,
+    settings: &Settings,
+    gamestate: &GameState,
+    animations: &TileAnimations,
+    now: f32,
+) -> Mesh {
+    let mut fills = ChunkMeshBuilder::default();
+    let mut glyphs = ChunkMeshBuilder::default();
+
+    for row in 0..tilemap.height {
+        for col in 0..tilemap.width {
+            let coordinates = Coordinates::new(col, row).with_layer(tilemap.view_layer + offset);
+            if !tilemap.contains(&coordinates) {
+                continue;
+            }
+
+            push_tile(
+                &mut fills,
+                &mut glyphs,
+                tilemap,
+                coordinates,
+                tilemap.rules.topology.tile_center(&coordinates),
+                settings,
+                gamestate,
+                animations,
+                now,
+                GHOST_ALPHA,
+            );
+        }
+    }
+
+    fills.append(glyphs);
+    fills.build()
+}
+
+/// adds the tile at `coordinates`, drawn around `center`: its fill to `fills`
+/// and the numbers and markers over it to `glyphs`, faded by `alpha`.
+fn push_tile(
+    fills: &mut ChunkMeshBuilder,
+    glyphs: &mut ChunkMeshBuilder,
+    tilemap: &TileMap,
+    coordinates: Coordinates,
+    center: Vec2,
+    settings: &Settings,
+    gamestate: &GameState,
+    animations: &TileAnimations,
+    now: f32,
+    alpha: f32,
+) {
+    let palette = settings.palette;
+    let (fill_glyph, fill_size) = tilemap.rules.topology.tile_fill();
+    let fade = |color: Color| color.with_alpha(color.alpha() * alpha);
+
+    let mut tile = &tilemap[coordinates];
+    let mut rect = Rect::from_center_size(center, Vec2::splat(TILE_SIZE));
+    let mut fill_rect = Rect::from_center_size(center, Vec2::splat(fill_size));
+
+    let hidden_tile;
+    if let Some(pose) = animations.pose(&coordinates, now) {
+        rect = Rect::from_center_size(center, rect.size() * pose.scale);
+        fill_rect = Rect::from_center_size(center, fill_rect.size() * pose.scale);
+
+        if pose.hidden {
+            hidden_tile = Tile::new(tile.shade());
+            tile = &hidden_tile;
+        }
+    }
+
+    let fill = palette.tile_color(tile, settings.shape_markers);
+    fills.push_glyph(fill_rect, fill_glyph, fade(fill));
+
+    if settings.shape_markers {
+        if tile.has_anti_flag() {
+            glyphs.push_glyph(rect, Glyph::AntiFlag, fade(palette.anti_mark_color()));
+        } else if tile.is_marked() {
+            glyphs.push_glyph(rect, Glyph::Flag, fade(palette.mark_color()));
+        } else if tile.is_flipped() && tile.contains_anti_mine() {
+            glyphs.push_glyph(rect, Glyph::AntiMine, fade(palette.anti_mine_color()));
+        } else if tile.is_flipped() && tile.contains_mine() {
+            glyphs.push_glyph(rect, Glyph::Mine, fade(palette.mine_color()));
+        }
+    }
+
+    // tiles holding several mines, and stacks of flags, show how many
+    let stack = match tile.is_flipped() {
+        true => tile.mines(),
+        false => tile.flags(),
+    };
+    if stack > 1 {
+        let corner = rect.center() + rect.size() * Vec2::new(0.3, -0.3);
+        let count = Rect::from_center_size(corner, rect.size() * 0.4);
+        glyphs.push_number(count, stack as i32, fade(Color::WHITE));
+    }
+
+    let wrong_anti_flag = tile.has_anti_flag() && !tile.contains_anti_mine();
+    if *gamestate == GameState::Lost && (tile.flags() > tile.mines() || wrong_anti_flag) {
+        glyphs.push_glyph(rect, Glyph::Cross, fade(Color::WHITE));
+    }
+
+    if tile.is_flipped() && !tile.contains_mine() {
+        match tile.number() {
+            Some(number) if number > 0 => {
+                let color = palette.number_color(number as usize);
+                glyphs.push_number(rect, number, fade(color));
+            }
+            Some(0) => glyphs.push_number(rect, 0, fade(palette.zero_color())),
+            // negative numbers are inverted, dark on a plate of their color
+            Some(number) => {
+                let color = palette.number_color(number.unsigned_abs() as usize);
+                let plate = Rect::from_center_size(rect.center(), rect.size() * 0.8);
+                glyphs.push_glyph(plate, Glyph::Solid, fade(color));
+                glyphs.push_number(rect, number, fade(Color::BLACK));
+            }
+            None => {}
+        }
+    }
+}
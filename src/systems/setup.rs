@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use crate::components::{Hover, LongPressIndicator};
+use crate::resources::{Coordinates, MeshHandles, Settings, TileMaterialHandles};
+use crate::FONT_PATH;
+
+#[derive(Resource, Debug)]
+pub struct FontHandle(pub Handle<Font>);
+
+pub fn setup_materials(
+    mut commands: Commands,
+    materials: ResMut<Assets<ColorMaterial>>,
+    settings: Res<Settings>,
+) {
+    let tile_material_handles = TileMaterialHandles::new(materials, settings.palette);
+    commands.insert_resource(tile_material_handles);
+}
+
+pub fn setup_mesh(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    settings: Res<Settings>,
+) {
+    // the first board is built from the settings as well
+    let tile_mesh = meshes.add(settings.rules.topology.tile_mesh());
+    commands.insert_resource(MeshHandles { tile_mesh });
+}
+
+pub fn load_font(mut commands: Commands, asset_server: ResMut<AssetServer>) {
+    let font = asset_server.load::<Font>(FONT_PATH);
+    commands.insert_resource(FontHandle(font));
+}
+
+pub fn setup_tilemap(mut commands: Commands, settings: Res<Settings>) {
+    commands.insert_resource(settings.new_tilemap());
+}
+
+pub fn spawn_hover_tile(
+    mut commands: Commands,
+    mesh_handles: Res<MeshHandles>,
+    material_handles: Res<TileMaterialHandles>,
+    settings: Res<Settings>,
+) {
+    let tile_mesh = mesh_handles.tile_mesh.clone();
+    let transform = Coordinates::new(0, 0).get_transform(settings.rules.topology, 1.0);
+
+    commands.spawn((
+        Hover,
+        Mesh2d(tile_mesh),
+        MeshMaterial2d(material_handles.hover.clone()),
+        transform,
+        Visibility::Hidden,
+    ));
+}
+
+pub fn spawn_long_press_indicator(
+    mut commands: Commands,
+    mesh_handles: Res<MeshHandles>,
+    material_handles: Res<TileMaterialHandles>,
+) {
+    commands.spawn((
+        LongPressIndicator,
+        Mesh2d(mesh_handles.tile_mesh.clone()),
+        MeshMaterial2d(material_handles.mark.clone()),
+        Transform::default(),
+        Visibility::Hidden,
+    ));
+}
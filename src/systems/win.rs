@@ -0,0 +1,228 @@
+use std::f32::consts::PI;
+
+use bevy::core_pipeline::bloom::Bloom;
+use bevy::prelude::*;
+
+use crate::components::ResultsCard;
+use crate::events::{BoardClearedEvent, Effect, SpawnEffectsEvent, TileChange, TileChangedEvent};
+use crate::resources::{
+    Autoplay, Coordinates, GameClock, GameState, PersonalBests, TileMap, WinSequence,
+};
+use crate::{BLOOM_INTENSITY, RECORDS_PATH};
+
+/// time the flags take to reach the farthest mine, in seconds.
+const FLAG_SECS: f32 = 1.0;
+/// delay before the particle wave starts, in seconds.
+const WAVE_DELAY_SECS: f32 = 0.6;
+/// time the wave takes to cross the board, in seconds.
+const WAVE_SECS: f32 = 1.2;
+/// delay before the results card shows up, in seconds.
+const CARD_DELAY_SECS: f32 = 2.5;
+/// bloom intensity added at the peak of a pulse.
+const PULSE_INTENSITY: f32 = 0.5;
+/// time between two bloom pulses, in seconds.
+const PULSE_PERIOD_SECS: f32 = 0.8;
+/// time over which the pulses die down, in seconds.
+const PULSE_FADE_SECS: f32 = 1.5;
+
+/// latest first, so entries that are due can be popped off the end.
+fn schedule(mut entries: Vec<(f32, Coordinates)>) -> Vec<(f32, Coordinates)> {
+    entries.sort_by(|a, b| b.0.total_cmp(&a.0));
+    entries
+}
+
+/// starts the celebration and records the result.
+pub fn handle_board_cleared(
+    mut board_cleared_events: EventReader<BoardClearedEvent>,
+    mut effects: EventWriter<SpawnEffectsEvent>,
+    mut sequence: ResMut<WinSequence>,
+    mut bests: ResMut<PersonalBests>,
+    autoplay: Res<Autoplay>,
+    clock: Res<GameClock>,
+    tilemap: Res<TileMap>,
+) {
+    let Some(&BoardClearedEvent {
+        coordinates: origin,
+    }) = board_cleared_events.read().last()
+    else {
+        return;
+    };
+
+    effects.send(SpawnEffectsEvent {
+        coordinates: origin,
+        effect: Effect::Confetti,
+    });
+
+    let unflagged: Vec<Coordinates> = tilemap
+        .iter()
+        .filter(|(_, tile)| {
+            tile.contains_mine()
+                && (tile.flags() != tile.mines()
+                    || tile.has_anti_flag() != tile.contains_anti_mine())
+        })
+        .map(|(coordinates, _)| coordinates)
+        .collect();
+    let farthest = unflagged
+        .iter()
+        .map(|coordinates| origin.distance(coordinates))
+        .fold(1.0, f32::max);
+    let flags = unflagged
+        .into_iter()
+        .map(|coordinates| {
+            (
+                FLAG_SECS * origin.distance(&coordinates) / farthest,
+                coordinates,
+            )
+        })
+        .collect();
+
+    let width = tilemap.width.max(1) as f32;
+    let wave = tilemap
+        .iter()
+        .map(|(coordinates, _)| {
+            let time = WAVE_DELAY_SECS + WAVE_SECS * coordinates.col as f32 / width;
+            (time, coordinates)
+        })
+        .collect();
+
+    let board = PersonalBests::board_key(&tilemap);
+    let previous_best = bests.get(&board);
+    // games the bot played a part in set no records
+    let new_best = !autoplay.played && bests.record(&board, clock.elapsed);
+    if new_best {
+        bests.save(RECORDS_PATH);
+    }
+
+    *sequence = WinSequence {
+        elapsed: 0.0,
+        flags: schedule(flags),
+        wave: schedule(wave),
+        time: clock.elapsed,
+        three_bv: tilemap.three_bv(),
+        previous_best,
+        new_best,
+    };
+}
+
+pub fn update_win_sequence(
+    time: Res<Time>,
+    gamestate: Res<GameState>,
+    mut sequence: ResMut<WinSequence>,
+    mut bloom: Single<&mut Bloom>,
+    mut effects: EventWriter<SpawnEffectsEvent>,
+    mut tile_changed_events: EventWriter<TileChangedEvent>,
+    mut tilemap: ResMut<TileMap>,
+) {
+    if *gamestate != GameState::Won {
+        return;
+    }
+
+    sequence.elapsed += time.delta_secs();
+    let elapsed = sequence.elapsed;
+
+    let pulse = (PI * elapsed / PULSE_PERIOD_SECS).sin().powi(2);
+    bloom.intensity =
+        BLOOM_INTENSITY + PULSE_INTENSITY * pulse * (-elapsed / PULSE_FADE_SECS).exp();
+
+    // flags are placed directly, since the board no longer takes input
+    while let Some(&(flag_at, coordinates)) = sequence.flags.last() {
+        if flag_at > elapsed {
+            break;
+        }
+
+        sequence.flags.pop();
+        let tile = &mut tilemap[coordinates];
+        let (mines, anti_mine) = (tile.mines(), tile.contains_anti_mine());
+        if tile.set_flags(mines) && tile.set_anti_flag(anti_mine) {
+            tile_changed_events.send(TileChangedEvent {
+                coordinates,
+                change: TileChange::Marked,
+            });
+            effects.send(SpawnEffectsEvent {
+                coordinates,
+                effect: Effect::Sparkle,
+            });
+        }
+    }
+
+    while let Some(&(pass_at, coordinates)) = sequence.wave.last() {
+        if pass_at > elapsed {
+            break;
+        }
+
+        sequence.wave.pop();
+        effects.send(SpawnEffectsEvent {
+            coordinates,
+            effect: Effect::Sparkle,
+        });
+    }
+}
+
+pub fn spawn_results_card(mut commands: Commands) {
+    commands
+        .spawn((
+            ResultsCard,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    padding: UiRect::all(Val::Px(24.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            ));
+        });
+}
+
+pub fn update_results_card(
+    gamestate: Res<GameState>,
+    sequence: Res<WinSequence>,
+    card: Single<(&mut Visibility, &Children), With<ResultsCard>>,
+    mut texts: Query<&mut Text>,
+) {
+    let (mut visibility, children) = card.into_inner();
+
+    let shown = *gamestate == GameState::Won && sequence.elapsed >= CARD_DELAY_SECS;
+    let target = match shown {
+        true => Visibility::Visible,
+        false => Visibility::Hidden,
+    };
+    if *visibility == target {
+        return;
+    }
+    *visibility = target;
+
+    let Some(mut text) = children
+        .first()
+        .and_then(|&child| texts.get_mut(child).ok())
+    else {
+        return;
+    };
+
+    let best = match (sequence.new_best, sequence.previous_best) {
+        (true, _) => "New personal best!".to_string(),
+        (false, Some(best)) => format!("Personal best: {best:.2}s"),
+        (false, None) => String::new(),
+    };
+    let per_second = sequence.three_bv as f32 / sequence.time.max(0.01);
+
+    text.0 = format!(
+        "Board cleared!\n\nTime: {:.2}s\n3BV: {} ({per_second:.2}/s)\n{best}\n\nStart a new game from the menu",
+        sequence.time, sequence.three_bv,
+    );
+}